@@ -0,0 +1,122 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use gennaro_dkg::*;
+use k256::ProjectivePoint;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+const SIZES: [usize; 4] = [3, 10, 50, 100];
+
+type Participants = Vec<SecretParticipant<ProjectivePoint>>;
+type Round1Data = (
+    BTreeMap<usize, Round1BroadcastData<ProjectivePoint>>,
+    BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+);
+
+fn new_participants(limit: usize) -> Participants {
+    let threshold = NonZeroUsize::new(limit / 2 + 1).unwrap();
+    let parameters = Parameters::new(threshold, NonZeroUsize::new(limit).unwrap());
+    (1..=limit)
+        .map(|id| SecretParticipant::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect()
+}
+
+fn round1(participants: &mut Participants) -> Round1Data {
+    let mut bdata = BTreeMap::new();
+    let mut p2p = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (b, data) = p.round1().unwrap();
+        bdata.insert(p.get_id(), b);
+        p2p.insert(p.get_id(), data);
+    }
+    (bdata, p2p)
+}
+
+fn round2_inputs(
+    id: usize,
+    (bdata, p2p): &Round1Data,
+) -> (
+    BTreeMap<usize, Round1BroadcastData<ProjectivePoint>>,
+    BTreeMap<usize, Round1P2PData>,
+) {
+    let b = bdata
+        .iter()
+        .filter(|(i, _)| **i != id)
+        .map(|(i, d)| (*i, d.clone()))
+        .collect();
+    let p = p2p
+        .iter()
+        .filter(|(i, _)| **i != id)
+        .map(|(i, d)| (*i, d[&id].clone()))
+        .collect();
+    (b, p)
+}
+
+fn round2(
+    participants: &mut Participants,
+    r1data: &Round1Data,
+) -> BTreeMap<usize, Round2EchoBroadcastData> {
+    let mut echo = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (b, p2p) = round2_inputs(p.get_id(), r1data);
+        echo.insert(p.get_id(), p.round2(b, p2p).unwrap());
+    }
+    echo
+}
+
+fn round3(
+    participants: &mut Participants,
+    echo: &BTreeMap<usize, Round2EchoBroadcastData>,
+) -> BTreeMap<usize, Round3BroadcastData<ProjectivePoint>> {
+    let mut bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        bdata.insert(p.get_id(), p.round3(echo).unwrap());
+    }
+    bdata
+}
+
+/// Time one participant's work in each round for a committee of each size
+fn rounds(c: &mut Criterion) {
+    let mut group = c.benchmark_group("k256");
+    group.sample_size(10);
+    for size in SIZES {
+        let mut participants = new_participants(size);
+        group.bench_with_input(BenchmarkId::new("round1", size), &participants, |b, ps| {
+            b.iter_batched(
+                || ps[0].clone(),
+                |mut p| p.round1().unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+
+        let r1data = round1(&mut participants);
+        group.bench_with_input(BenchmarkId::new("round2", size), &participants, |b, ps| {
+            b.iter_batched(
+                || (ps[0].clone(), round2_inputs(1, &r1data)),
+                |(mut p, (bdata, p2p))| p.round2(bdata, p2p).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+
+        let echo = round2(&mut participants, &r1data);
+        group.bench_with_input(BenchmarkId::new("round3", size), &participants, |b, ps| {
+            b.iter_batched(
+                || ps[0].clone(),
+                |mut p| p.round3(&echo).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+
+        let r3data = round3(&mut participants, &echo);
+        group.bench_with_input(BenchmarkId::new("round4", size), &participants, |b, ps| {
+            b.iter_batched(
+                || ps[0].clone(),
+                |mut p| p.round4(&r3data).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, rounds);
+criterion_main!(benches);
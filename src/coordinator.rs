@@ -0,0 +1,106 @@
+use crate::{
+    deserialize_g, serialize_g, Parameters, Round, Round2EchoBroadcastData, SchnorrSignature,
+};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+const VALID_SET_DOMAIN: &[u8] = b"gennaro-dkg coordinator valid set";
+const PUBLIC_KEY_DOMAIN: &[u8] = b"gennaro-dkg coordinator public key";
+
+/// The valid participant set attested to by the coordinator.
+///
+/// In coordinated mode this replaces the round 2 echo broadcast,
+/// see [`Participant::round3_coordinated`](crate::Participant::round3_coordinated).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedValidSet<G: Group + GroupEncoding + Default> {
+    /// The valid participant set
+    pub echo: Round2EchoBroadcastData,
+    /// The coordinator's signature over the set
+    #[serde(bound(serialize = "SchnorrSignature<G>: Serialize"))]
+    #[serde(bound(deserialize = "SchnorrSignature<G>: Deserialize<'de>"))]
+    pub signature: SchnorrSignature<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> SignedValidSet<G> {
+    /// Sign `echo` for the session using `parameters` with the coordinator's `secret_key`.
+    ///
+    /// The signature covers [`Parameters::fingerprint`] so it cannot be
+    /// replayed in a session with different parameters.
+    pub fn sign(
+        parameters: &Parameters<G>,
+        echo: Round2EchoBroadcastData,
+        secret_key: &G::Scalar,
+        rng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let signature = SchnorrSignature::sign(secret_key, &Self::message(parameters, &echo), rng);
+        Self { echo, signature }
+    }
+
+    /// Check the signature for the session using `parameters`
+    /// with the coordinator's `public_key`
+    pub fn verify(&self, parameters: &Parameters<G>, public_key: G) -> bool {
+        self.signature
+            .verify(public_key, &Self::message(parameters, &self.echo))
+    }
+
+    fn message(parameters: &Parameters<G>, echo: &Round2EchoBroadcastData) -> Vec<u8> {
+        let mut message = VALID_SET_DOMAIN.to_vec();
+        message.extend_from_slice(&parameters.fingerprint());
+        message.push(Round::Three.into());
+        for id in &echo.valid_participant_ids {
+            message.extend_from_slice(&(*id as u64).to_be_bytes());
+        }
+        message
+    }
+}
+
+/// The group public key attested to by the coordinator.
+///
+/// In coordinated mode this replaces the round 4 echo broadcast,
+/// see [`Participant::round5_coordinated`](crate::Participant::round5_coordinated).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedPublicKey<G: Group + GroupEncoding + Default> {
+    /// The group public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The coordinator's signature over the key
+    #[serde(bound(serialize = "SchnorrSignature<G>: Serialize"))]
+    #[serde(bound(deserialize = "SchnorrSignature<G>: Deserialize<'de>"))]
+    pub signature: SchnorrSignature<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> SignedPublicKey<G> {
+    /// Sign `public_key` for the session using `parameters` with the coordinator's `secret_key`.
+    ///
+    /// The signature covers [`Parameters::fingerprint`] so it cannot be
+    /// replayed in a session with different parameters.
+    pub fn sign(
+        parameters: &Parameters<G>,
+        public_key: G,
+        secret_key: &G::Scalar,
+        rng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let signature =
+            SchnorrSignature::sign(secret_key, &Self::message(parameters, public_key), rng);
+        Self {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Check the signature for the session using `parameters`
+    /// with the coordinator's `public_key`
+    pub fn verify(&self, parameters: &Parameters<G>, public_key: G) -> bool {
+        self.signature
+            .verify(public_key, &Self::message(parameters, self.public_key))
+    }
+
+    fn message(parameters: &Parameters<G>, public_key: G) -> Vec<u8> {
+        let mut message = PUBLIC_KEY_DOMAIN.to_vec();
+        message.extend_from_slice(&parameters.fingerprint());
+        message.push(Round::Five.into());
+        message.extend_from_slice(public_key.to_bytes().as_ref());
+        message
+    }
+}
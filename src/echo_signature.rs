@@ -0,0 +1,45 @@
+use crate::{from_bare_bytes, to_bare_bytes, DkgResult, SchnorrSignature};
+use rand_core::{CryptoRng, RngCore};
+use std::collections::BTreeSet;
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+const ECHO_DOMAIN: &[u8] = b"gennaro-dkg signed echo";
+
+/// Sign the valid set `ids` chosen by `signer` with its echo key.
+///
+/// The echo key is a random key chosen for the session, its public half
+/// is sent in round 1 so the signature is bound to the signer's contribution.
+/// The output is the binary encoding of a [`SchnorrSignature`].
+pub(crate) fn sign_echo<G: Group + GroupEncoding + Default>(
+    signer: usize,
+    ids: &BTreeSet<usize>,
+    echo_key: &G::Scalar,
+    rng: impl RngCore + CryptoRng,
+) -> DkgResult<Vec<u8>> {
+    to_bare_bytes(&SchnorrSignature::<G>::sign(
+        echo_key,
+        &message(signer, ids),
+        rng,
+    ))
+}
+
+/// Check a signature created by [`sign_echo`] against the
+/// signer's public echo key from round 1
+pub(crate) fn verify_echo<G: Group + GroupEncoding + Default>(
+    signer: usize,
+    ids: &BTreeSet<usize>,
+    signature: &[u8],
+    echo_key: G,
+) -> bool {
+    from_bare_bytes::<SchnorrSignature<G>>(signature)
+        .is_ok_and(|signature| signature.verify(echo_key, &message(signer, ids)))
+}
+
+fn message(signer: usize, ids: &BTreeSet<usize>) -> Vec<u8> {
+    let mut message = ECHO_DOMAIN.to_vec();
+    message.extend_from_slice(&(signer as u64).to_be_bytes());
+    for id in ids {
+        message.extend_from_slice(&(*id as u64).to_be_bytes());
+    }
+    message
+}
@@ -1,24 +1,137 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use thiserror::Error as DError;
 
 /// Errors produced by the gennaro DKG
-#[derive(DError, Debug)]
+///
+/// Errors are `Clone` so they can be sent across channels.
+#[derive(DError, Clone, Debug)]
 pub enum Error {
     /// Format errors
     #[error("fmt error")]
     FmtError(#[from] std::fmt::Error),
-    /// Io errors
-    #[error("io error")]
-    IoError(#[from] std::io::Error),
+    /// Io errors, holds the message of the original error
+    #[error("io error: {0}")]
+    IoError(String),
     /// Verifiable secret sharing scheme errors
-    #[error("vsss error")]
+    #[error("vsss error: {0:?}")]
     VsssError(vsss_rs::Error),
+    /// Errors serializing or deserializing data
+    #[error("serialization error: {0}")]
+    SerializationError(String),
+    /// Invalid arguments to a helper function
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
     /// Errors during secret_participant initialization
     #[error("error during secret_participant creation: {0}")]
     InitializationError(String),
     /// Errors using rounds
     #[error("round {0} invalid input: `{1}`")]
     RoundError(usize, String),
+    /// A round function was called when the participant is in a different round
+    #[error("round {expected} was called but the participant is in round {got}")]
+    RoundOrder {
+        /// The round that was called
+        expected: usize,
+        /// The round the participant is in
+        got: usize,
+    },
+    /// Fewer valid participants remain than are needed to reconstruct the secret
+    #[error("only {have} valid participants remain but {need} are needed")]
+    InsufficientParticipants {
+        /// The number of valid participants
+        have: usize,
+        /// The threshold
+        need: usize,
+    },
+    /// Parameters differ from the ones expected
+    #[error("parameters differ in `{field}`")]
+    ParametersMismatch {
+        /// The name of the first field that differs
+        field: &'static str,
+    },
+    /// The protocol cannot continue
+    #[error("the protocol aborted: {0}")]
+    Abort(AbortReason),
+    /// A participant sent broadcast data but no peer-to-peer data
+    #[error("secret_participant {from} sent broadcast data but no peer-to-peer data")]
+    MissingP2PData {
+        /// The id of the participant
+        from: usize,
+    },
+    /// A participant sent peer-to-peer data but no broadcast data
+    #[error("secret_participant {from} sent peer-to-peer data but no broadcast data")]
+    MissingBroadcastData {
+        /// The id of the participant
+        from: usize,
+    },
+    /// A participant sent malformed commitments
+    #[error("secret_participant {from} sent invalid commitments")]
+    InvalidCommitment {
+        /// The id of the participant that sent the commitments
+        from: usize,
+    },
+    /// Several participants sent the same round 1 commitments
+    #[error("secret_participants {ids:?} sent identical commitments")]
+    DuplicateCommitments {
+        /// The ids of the participants that sent the same commitments
+        ids: BTreeSet<usize>,
+    },
+    /// A participant sent different round 1 commitments to different participants
+    #[error("secret_participant {id} sent inconsistent commitments in round 1 and round 3")]
+    Equivocation {
+        /// The id of the equivocating participant
+        id: usize,
+    },
+    /// The combined commitment polynomial has a lower degree than `threshold - 1`
+    /// so fewer than `threshold` shares are enough to reconstruct the secret
+    #[error("the combined polynomial has a degree lower than threshold - 1")]
+    DegradedThreshold,
+    /// The key material was requested before the protocol completed
+    #[error("the protocol has not completed")]
+    NotComplete,
+    /// The computed secret share does not match the combined commitments
+    #[error("the computed secret share does not match the public commitments")]
+    ShareSelfCheckFailed,
+    /// The public key is not in the prime-order subgroup
+    #[error("the public key is not in the prime-order subgroup")]
+    InvalidSubgroup,
+    /// The dealers sent shares that failed verification and too few valid
+    /// participants remain, holds the ids of every bad dealer
+    #[error("secret_participants {0:?} sent invalid shares")]
+    MultipleBadDealers(BTreeSet<usize>),
+    /// A participant's data is not signed by its verifying key
+    #[error("secret_participant {from} sent data with an invalid signature")]
+    BadSignature {
+        /// The id of the participant that sent the data
+        from: usize,
+    },
+    /// The valid participant set must be approved before round 3
+    #[error("the valid participant set has not been approved")]
+    AwaitingApproval,
+    /// The secret share was handed to a share sink
+    #[error("the secret share is held by a share sink")]
+    ShareInSink,
+    /// A verifier-only participant holds no secret share
+    #[error("a verifier-only participant holds no secret share")]
+    VerifierOnly,
+    /// The entropy source failed
+    #[error("the entropy source failed: {0}")]
+    RngFailure(String),
+    /// A participant's verification share does not match the combined commitments
+    #[error(
+        "secret_participant {from} sent a verification share that does not match the commitments"
+    )]
+    InvalidVerificationShare {
+        /// The id of the participant that sent the verification share
+        from: usize,
+    },
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(value.to_string())
+    }
 }
 
 impl From<vsss_rs::Error> for Error {
@@ -27,6 +140,17 @@ impl From<vsss_rs::Error> for Error {
     }
 }
 
+/// Why the protocol aborted
+#[derive(DError, Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AbortReason {
+    /// Every participant is required but at least one was dropped
+    #[error("every participant is required but at least one is invalid")]
+    TooFewValidParticipants,
+    /// More participants were dropped than allowed by [`crate::Parameters::with_max_faults`]
+    #[error("more participants are faulty than the configured maximum")]
+    TooManyFaults,
+}
+
 /// Dkg results
 pub type DkgResult<T> = anyhow::Result<T, Error>;
 
@@ -0,0 +1,24 @@
+use sha2::{Digest, Sha256};
+
+/// The hash function used to derive generators and transcripts.
+///
+/// [`Sha256Hasher`] is the default. Deployments that need a hash that is
+/// cheap inside a circuit, like Poseidon, can provide their own.
+pub trait DkgHasher {
+    /// Hash the concatenation of `inputs` to 32 bytes
+    fn digest(inputs: &[&[u8]]) -> [u8; 32];
+}
+
+/// SHA-256 hasher
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Sha256Hasher;
+
+impl DkgHasher for Sha256Hasher {
+    fn digest(inputs: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for input in inputs {
+            hasher.update(input);
+        }
+        hasher.finalize().into()
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    deserialize_g, deserialize_g_map, deserialize_scalar, serialize_g, serialize_g_map,
+    serialize_scalar, Parameters,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter};
+use vsss_rs::elliptic_curve::{ff::Field, group::GroupEncoding, Group};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The key material held by a participant after the DKG has completed.
+///
+/// This bundles everything a threshold signer needs to create
+/// and verify partial signatures. The secret share is redacted
+/// from the `Debug` output and cleared when the package is dropped.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyPackage<G: Group + GroupEncoding + Default> {
+    /// The identifier of the participant holding this package
+    pub identifier: usize,
+    /// The participant's secret share
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub secret_share: G::Scalar,
+    /// The group public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The public verification share of each valid participant
+    #[serde(
+        serialize_with = "serialize_g_map",
+        deserialize_with = "deserialize_g_map"
+    )]
+    pub verification_shares: BTreeMap<usize, G>,
+}
+
+impl<G: Group + GroupEncoding + Default> Debug for KeyPackage<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPackage")
+            .field("identifier", &self.identifier)
+            .field("secret_share", &"<redacted>")
+            .field("public_key", &self.public_key)
+            .field("verification_shares", &self.verification_shares)
+            .finish()
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Zeroize for KeyPackage<G> {
+    fn zeroize(&mut self) {
+        self.secret_share = G::Scalar::ZERO;
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Drop for KeyPackage<G> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> ZeroizeOnDrop for KeyPackage<G> {}
+
+/// The public key material of a completed DKG.
+///
+/// This is what a node needs to verify partial signatures without holding
+/// a share. It contains no secret material so it is safe to distribute widely.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PublicKeyPackage<G: Group + GroupEncoding + Default> {
+    /// The group public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The public verification share of each valid participant
+    #[serde(
+        serialize_with = "serialize_g_map",
+        deserialize_with = "deserialize_g_map"
+    )]
+    pub verification_shares: BTreeMap<usize, G>,
+    /// The parameters used to generate the key
+    #[serde(bound(serialize = "Parameters<G>: Serialize"))]
+    #[serde(bound(deserialize = "Parameters<G>: Deserialize<'de>"))]
+    pub parameters: Parameters<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> PublicKeyPackage<G> {
+    /// Derive the public key package of the child key created by
+    /// [`Participant::tweak_add`](crate::Participant::tweak_add) with the same `tweak`.
+    ///
+    /// The group public key and every verification share are offset by
+    /// `tweak` times the message generator, no secret is needed.
+    pub fn tweak_public_key(&mut self, tweak: G::Scalar) {
+        let offset = self.parameters.message_generator * tweak;
+        self.public_key += offset;
+        for share in self.verification_shares.values_mut() {
+            *share += offset;
+        }
+    }
+}
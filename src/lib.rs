@@ -195,33 +195,59 @@
 pub use rand_core;
 pub use vsss_rs;
 
+mod coordinator;
+#[cfg(feature = "signed-echo")]
+mod echo_signature;
 mod error;
+mod hasher;
+mod key_package;
+mod manifest;
 mod parameters;
 mod participant;
 mod pedersen_result;
+mod pop;
+pub mod presets;
 mod protected;
+mod registry;
+mod schema;
+mod schnorr;
 mod secret_share;
+mod secret_store;
+#[cfg(any(test, feature = "test-util"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod test_util;
+mod utils;
 
-use rand_core::SeedableRng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
 use serde::{
-    de::{Error as DError, SeqAccess, Unexpected, Visitor},
+    de::{DeserializeOwned, Error as DError, SeqAccess, Unexpected, Visitor},
     ser::{SerializeSeq, SerializeTuple},
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Display, Formatter},
     marker::PhantomData,
     num::NonZeroUsize,
 };
 use uint_zigzag::Uint;
-use vsss_rs::elliptic_curve::{group::GroupEncoding, Group, PrimeField};
+use vsss_rs::elliptic_curve::{group::GroupEncoding, subtle::ConstantTimeEq, Group, PrimeField};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+pub use coordinator::*;
 pub use error::*;
+pub use hasher::*;
+pub use key_package::*;
+pub use manifest::*;
 pub use parameters::*;
 pub use participant::*;
 pub use pedersen_result::*;
+pub use pop::*;
+pub use registry::*;
+pub use schema::*;
+pub use schnorr::*;
+pub use secret_store::*;
+pub use utils::*;
 
 /// Valid rounds
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -250,6 +276,19 @@ impl Display for Round {
     }
 }
 
+impl Round {
+    /// A human readable description of the round
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::One => "Round 1: share polynomials",
+            Self::Two => "Round 2: verify shares",
+            Self::Three => "Round 3: agree on valid participants",
+            Self::Four => "Round 4: compute public key",
+            Self::Five => "Round 5: confirm public key",
+        }
+    }
+}
+
 macro_rules! impl_round_to_int {
     ($ident:ident) => {
         impl From<Round> for $ident {
@@ -273,17 +312,91 @@ impl_round_to_int!(u64);
 impl_round_to_int!(usize);
 
 /// Broadcast data from round 1 that should be sent to all other participants
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// JSON wire format, shown for secp256k1 with a threshold of 2:
+///
+/// ```json
+/// {
+///   "message_generator": "Anm-Zn753LusVaBilc6HCwcCm_zbLc4o2VnygVsW-BeY",
+///   "blinder_generator": "Aly98GRuXbTqo5jzZfLqeg49QZt-AzDjnOkr3e3KxPm8",
+///   "pedersen_commitments": [
+///     "A-zJmwz4nvFBJxgZfvF-0IdvAsJPuxCuRt8FG3naFLbD",
+///     "An5iRpwIk_wWYfoESSUM0qV1WLno1GEwwSUUnu2Y_hJJ"
+///   ]
+/// }
+/// ```
+///
+/// With the `signed-echo` feature an `echo_key` field is added that
+/// holds the key the sender signs its round 2 echo with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Round1BroadcastData<G: Group + GroupEncoding + Default> {
-    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    #[serde(
+        rename = "message_generator",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
     message_generator: G,
-    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    #[serde(
+        rename = "blinder_generator",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
     blinder_generator: G,
     #[serde(
+        rename = "pedersen_commitments",
         serialize_with = "serialize_g_vec",
         deserialize_with = "deserialize_g_vec"
     )]
     pedersen_commitments: Vec<G>,
+    #[cfg(feature = "signed-echo")]
+    #[serde(
+        rename = "echo_key",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
+    echo_key: G,
+}
+
+impl<G: Group + GroupEncoding + Default> Round1BroadcastData<G> {
+    /// The fields of the JSON wire format
+    pub fn schema() -> &'static [FieldSchema] {
+        &[
+            FieldSchema {
+                name: "message_generator",
+                encoding: POINT_ENCODING,
+                description: "the generator used for the secret polynomial",
+            },
+            FieldSchema {
+                name: "blinder_generator",
+                encoding: POINT_ENCODING,
+                description: "the generator used for the blinding polynomial",
+            },
+            FieldSchema {
+                name: "pedersen_commitments",
+                encoding: POINT_ARRAY_ENCODING,
+                description:
+                    "the pedersen commitments to the polynomial coefficients, `threshold` entries",
+            },
+            #[cfg(feature = "signed-echo")]
+            FieldSchema {
+                name: "echo_key",
+                encoding: POINT_ENCODING,
+                description: "the public key that signs the sender's round 2 echo",
+            },
+        ]
+    }
+
+    /// Serialize to the binary wire format
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        to_bare_bytes(self)
+    }
+
+    /// Deserialize from the binary wire format.
+    ///
+    /// Malformed input returns an error and never panics.
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        from_bare_bytes(bytes)
+    }
 }
 
 #[cfg(test)]
@@ -294,9 +407,98 @@ impl<G: Group + GroupEncoding + Default> serde_encrypt::traits::SerdeEncryptShar
 }
 
 /// Echo broadcast data from round 2 that should be sent to all valid participants
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// JSON wire format:
+///
+/// ```json
+/// {
+///   "valid_participant_ids": [1, 2, 3]
+/// }
+/// ```
+///
+/// With the `signed-echo` feature a `signature` field is added that
+/// holds the sender's signature over the set or `null`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Round2EchoBroadcastData {
+    #[serde(rename = "valid_participant_ids")]
     valid_participant_ids: BTreeSet<usize>,
+    #[cfg(feature = "signed-echo")]
+    #[serde(rename = "signature", default)]
+    signature: Option<Vec<u8>>,
+}
+
+impl Round2EchoBroadcastData {
+    /// Create echo broadcast data with the specified valid participant ids
+    pub fn new(valid_participant_ids: BTreeSet<usize>) -> Self {
+        Self {
+            valid_participant_ids,
+            #[cfg(feature = "signed-echo")]
+            signature: None,
+        }
+    }
+
+    /// The ids of the participants that passed round 2 verification
+    pub fn valid_participant_ids(&self) -> &BTreeSet<usize> {
+        &self.valid_participant_ids
+    }
+
+    /// The sender's signature over the valid set, if it was signed
+    #[cfg(feature = "signed-echo")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signed-echo")))]
+    pub fn signature(&self) -> Option<&[u8]> {
+        self.signature.as_deref()
+    }
+
+    /// Check that `signer` signed this valid set with the echo key
+    /// in `round1`, the broadcast data it sent in round 1.
+    ///
+    /// This lets an auditor prove which participants agreed to the valid set.
+    /// Returns false if there is no signature.
+    #[cfg(feature = "signed-echo")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signed-echo")))]
+    pub fn verify_signature<G: Group + GroupEncoding + Default>(
+        &self,
+        signer: usize,
+        round1: &Round1BroadcastData<G>,
+    ) -> bool {
+        self.signature.as_ref().is_some_and(|signature| {
+            echo_signature::verify_echo(
+                signer,
+                &self.valid_participant_ids,
+                signature,
+                round1.echo_key,
+            )
+        })
+    }
+
+    /// The fields of the JSON wire format
+    pub fn schema() -> &'static [FieldSchema] {
+        &[
+            FieldSchema {
+                name: "valid_participant_ids",
+                encoding: "array of numbers, sorted ascending",
+                description: "the ids of the participants that passed round 2 verification",
+            },
+            #[cfg(feature = "signed-echo")]
+            FieldSchema {
+                name: "signature",
+                encoding: "array of bytes or null",
+                description: "the sender's signature over the valid participant ids",
+            },
+        ]
+    }
+
+    /// Serialize to the binary wire format
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        to_bare_bytes(self)
+    }
+
+    /// Deserialize from the binary wire format.
+    ///
+    /// Malformed input returns an error and never panics.
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        from_bare_bytes(bytes)
+    }
 }
 
 #[cfg(test)]
@@ -305,30 +507,454 @@ impl serde_encrypt::traits::SerdeEncryptSharedKey for Round1P2PData {
 }
 
 /// Broadcast data from round 3 that should be sent to all valid participants
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// JSON wire format, shown for secp256k1 with a threshold of 2:
+///
+/// ```json
+/// {
+///   "commitments": [
+///     "Anm-Zn753LusVaBilc6HCwcCm_zbLc4o2VnygVsW-BeY",
+///     "A3dK5_hYqUEeXvQka3DGWqxWSZgL5cF4kbvsF4ldoAjL"
+///   ],
+///   "pedersen_commitments": [
+///     "A-zJmwz4nvFBJxgZfvF-0IdvAsJPuxCuRt8FG3naFLbD",
+///     "An5iRpwIk_wWYfoESSUM0qV1WLno1GEwwSUUnu2Y_hJJ"
+///   ],
+///   "verification_share": "Av6NHrG8s0MrHbWDP_XyIm2cteZc7kMFWMGO06PIbOGv"
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Round3BroadcastData<G: Group + GroupEncoding + Default> {
     #[serde(
+        rename = "commitments",
         serialize_with = "serialize_g_vec",
         deserialize_with = "deserialize_g_vec"
     )]
     commitments: Vec<G>,
+    #[serde(
+        rename = "pedersen_commitments",
+        serialize_with = "serialize_g_vec",
+        deserialize_with = "deserialize_g_vec"
+    )]
+    pedersen_commitments: Vec<G>,
+    #[serde(
+        rename = "verification_share",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
+    verification_share: G,
+}
+
+impl<G: Group + GroupEncoding + Default> Round3BroadcastData<G> {
+    /// The sender's verification share `g^{s_j}` of the secret share
+    /// it holds after round 3.
+    ///
+    /// Any `threshold` of these interpolate to the public key,
+    /// see [`Participant::round4_with_quorum`].
+    pub fn verification_share(&self) -> G {
+        self.verification_share
+    }
+
+    /// The sender's commitments to its blinder polynomial `h^{b_j}`.
+    ///
+    /// These are the pedersen commitments divided by the feldman commitments
+    /// so an auditor can re-check `g^{a_j} * h^{b_j} = C_j` independently.
+    /// Returns [`None`] if the two lists have different lengths.
+    pub fn blinder_commitments(&self) -> Option<Vec<G>> {
+        if self.commitments.len() != self.pedersen_commitments.len() {
+            return None;
+        }
+        Some(
+            self.pedersen_commitments
+                .iter()
+                .zip(self.commitments.iter())
+                .map(|(p, c)| *p - c)
+                .collect(),
+        )
+    }
+
+    /// The fields of the JSON wire format
+    pub fn schema() -> &'static [FieldSchema] {
+        &[
+            FieldSchema {
+                name: "commitments",
+                encoding: POINT_ARRAY_ENCODING,
+                description:
+                    "the feldman commitments to the secret polynomial coefficients, `threshold` entries",
+            },
+            FieldSchema {
+                name: "pedersen_commitments",
+                encoding: POINT_ARRAY_ENCODING,
+                description: "the sender's round 1 pedersen commitments, repeated to detect equivocation",
+            },
+            FieldSchema {
+                name: "verification_share",
+                encoding: POINT_ENCODING,
+                description: "the sender's public verification share `g^{s_j}`",
+            },
+        ]
+    }
+
+    /// Serialize to the binary wire format
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        to_bare_bytes(self)
+    }
+
+    /// Deserialize from the binary wire format.
+    ///
+    /// Malformed input returns an error and never panics.
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        from_bare_bytes(bytes)
+    }
 }
 
 /// Echo broadcast data from round 4 that should be sent to all valid participants
-#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+///
+/// JSON wire format, shown for secp256k1:
+///
+/// ```json
+/// {
+///   "public_key": "A__5e9V1Xu6kIEU6FDVSNdOC9kcvhWihiy8FehRgKXVW"
+/// }
+/// ```
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Round4EchoBroadcastData<G: Group + GroupEncoding + Default> {
     /// The computed public key
-    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    #[serde(
+        rename = "public_key",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
     pub public_key: G,
 }
 
+impl<G: Group + GroupEncoding + Default> Round4EchoBroadcastData<G> {
+    /// The fields of the JSON wire format
+    pub fn schema() -> &'static [FieldSchema] {
+        &[FieldSchema {
+            name: "public_key",
+            encoding: POINT_ENCODING,
+            description: "the group public key computed by the sender",
+        }]
+    }
+
+    /// Serialize to the binary wire format
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        to_bare_bytes(self)
+    }
+
+    /// Deserialize from the binary wire format.
+    ///
+    /// Malformed input returns an error and never panics.
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        from_bare_bytes(bytes)
+    }
+}
+
 /// Peer data from round 1 that should only be sent to a specific secret_participant
+///
+/// Both shares use the encoding of [`encode_share`]: the first byte is the
+/// recipient's id followed by the scalar bytes, on every curve.
+///
+/// JSON wire format, shown for secp256k1 and sent to participant 2:
+///
+/// ```json
+/// {
+///   "secret_share": [2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 23],
+///   "blind_share": [2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 83]
+/// }
+/// ```
 #[derive(Clone, Debug, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct Round1P2PData {
+    #[serde(rename = "secret_share")]
     secret_share: Vec<u8>,
+    #[serde(rename = "blind_share")]
     blind_share: Vec<u8>,
 }
 
+// Shares are secret so compare them in constant time
+impl PartialEq for Round1P2PData {
+    fn eq(&self, other: &Self) -> bool {
+        (self.secret_share.ct_eq(&other.secret_share) & self.blind_share.ct_eq(&other.blind_share))
+            .into()
+    }
+}
+
+impl Eq for Round1P2PData {}
+
+impl Round1P2PData {
+    /// The fields of the JSON wire format
+    pub fn schema() -> &'static [FieldSchema] {
+        &[
+            FieldSchema {
+                name: "secret_share",
+                encoding: SHARE_ENCODING,
+                description: "the recipient's share of the sender's secret polynomial",
+            },
+            FieldSchema {
+                name: "blind_share",
+                encoding: SHARE_ENCODING,
+                description: "the recipient's share of the sender's blinding polynomial",
+            },
+        ]
+    }
+
+    /// Serialize to the binary wire format
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        to_bare_bytes(self)
+    }
+
+    /// Deserialize from the binary wire format.
+    ///
+    /// Malformed input returns an error and never panics.
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        from_bare_bytes(bytes)
+    }
+}
+
+/// Peer data from round 1 encrypted to the recipient's long-term public key
+///
+/// The shares are encrypted with ChaCha20-Poly1305 under a key derived
+/// from an ephemeral Diffie-Hellman exchange over the DKG curve so it can
+/// be sent over a channel that is not confidential.
+#[cfg(feature = "encrypted-shares")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encrypted-shares")))]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedRound1P2PData<G: Group + GroupEncoding + Default> {
+    /// The sender's ephemeral public key
+    #[serde(
+        rename = "ephemeral_key",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
+    pub ephemeral_key: G,
+    /// The encrypted secret share and blind share
+    #[serde(rename = "ciphertext")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// The round 1 broadcast data and the encrypted peer-to-peer data keyed by
+/// recipient, see [`Participant::round1_encrypted`]
+#[cfg(feature = "encrypted-shares")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encrypted-shares")))]
+pub type EncryptedRound1Output<G> = (
+    Round1BroadcastData<G>,
+    BTreeMap<usize, EncryptedRound1P2PData<G>>,
+);
+
+/// Round data signed by the sender's long-term signing key.
+///
+/// The signature covers the sender id, the recipient id, which is 0 for
+/// broadcast data, and the binary encoding of `data`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize, SchnorrSignature<G>: Serialize",
+    deserialize = "T: Deserialize<'de>, SchnorrSignature<G>: Deserialize<'de>"
+))]
+pub struct Authenticated<T, G: Group + GroupEncoding + Default> {
+    /// The round data
+    pub data: T,
+    /// The sender's signature over the data
+    pub signature: SchnorrSignature<G>,
+}
+
+impl<T: Serialize, G: Group + GroupEncoding + Default> Authenticated<T, G> {
+    /// Sign `data` sent from `from` to `to` with `signing_key`
+    pub(crate) fn sign(
+        data: T,
+        from: usize,
+        to: usize,
+        signing_key: &G::Scalar,
+        rng: impl RngCore + CryptoRng,
+    ) -> DkgResult<Self> {
+        let message = Self::message(&data, from, to)?;
+        let signature = SchnorrSignature::sign(signing_key, &message, rng);
+        Ok(Self { data, signature })
+    }
+
+    /// Check that `from` signed this data for `to` with the key `verifying_key`
+    pub fn verify(&self, from: usize, to: usize, verifying_key: G) -> bool {
+        Self::message(&self.data, from, to)
+            .map(|message| self.signature.verify(verifying_key, &message))
+            .unwrap_or(false)
+    }
+
+    fn message(data: &T, from: usize, to: usize) -> DkgResult<Vec<u8>> {
+        let mut message = b"gennaro-dkg authenticated round data".to_vec();
+        message.extend_from_slice(&(from as u64).to_be_bytes());
+        message.extend_from_slice(&(to as u64).to_be_bytes());
+        message.extend_from_slice(&to_bare_bytes(data)?);
+        Ok(message)
+    }
+}
+
+/// The signed round 1 broadcast data and peer-to-peer data keyed by recipient,
+/// see [`Participant::round1_authenticated`]
+pub type AuthenticatedRound1Output<G> = (
+    Authenticated<Round1BroadcastData<G>, G>,
+    BTreeMap<usize, Authenticated<Round1P2PData, G>>,
+);
+
+/// The binary encoded size of the messages sent in a round,
+/// see [`Participant::message_sizes`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RoundSizes {
+    /// The round the messages are sent in
+    pub round: Round,
+    /// The size of the broadcast message in bytes
+    pub broadcast: usize,
+    /// The total size of the peer-to-peer messages in bytes
+    pub p2p: usize,
+}
+
+/// A snapshot of a participant's protocol state without any secret material,
+/// see [`Participant::diagnostics`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// The participant's id
+    pub id: usize,
+    /// The round the participant runs next
+    pub round: Round,
+    /// The threshold
+    pub threshold: usize,
+    /// The limit
+    pub limit: usize,
+    /// The hex encoded [`Parameters::fingerprint`]
+    pub parameters_fingerprint: String,
+    /// The number of round 1 broadcasts received
+    pub broadcasts_received: usize,
+    /// The number of round 1 peer-to-peer messages received
+    pub p2p_received: usize,
+    /// The number of peer-to-peer messages preloaded before round 2
+    pub p2p_preloaded: usize,
+    /// The number of round 2 echoes added before round 3
+    pub echoes_received: usize,
+    /// The valid participant ids
+    pub valid_participant_ids: BTreeSet<usize>,
+    /// The ids that sent round 1 data but are not valid participants
+    pub dropped_ids: BTreeSet<usize>,
+    /// Whether the participant only verifies and holds no share
+    pub verifier_only: bool,
+}
+
+/// The result of comparing valid sets after a network partition heals,
+/// see [`Participant::reconcile`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReconcileOutcome {
+    /// The valid set was narrowed to the participants both sides accepted
+    Merged,
+    /// The sides cannot agree on a valid set and the DKG must be restarted
+    AbortRequired,
+    /// The valid set is unchanged
+    NoChange,
+}
+
+/// The output of round 1
+#[derive(Clone, Debug)]
+pub struct Round1Output<G: Group + GroupEncoding + Default> {
+    /// The data to send to all other participants
+    pub broadcast: Round1BroadcastData<G>,
+    /// The data to send to each participant over a private channel, keyed by recipient id
+    pub p2p: BTreeMap<usize, Round1P2PData>,
+}
+
+impl<G: Group + GroupEncoding + Default> Round1Output<G> {
+    /// The peer-to-peer data to send to participant `id`
+    pub fn p2p_for(&self, id: usize) -> Option<&Round1P2PData> {
+        self.p2p.get(&id)
+    }
+
+    /// Every message to send in round 1 with its recipient.
+    ///
+    /// The broadcast comes first followed by the peer-to-peer data
+    /// in ascending recipient order.
+    pub fn outgoing(&self) -> impl Iterator<Item = (Recipient, MessagePayload<G>)> + '_ {
+        std::iter::once((
+            Recipient::All,
+            MessagePayload::Round1Broadcast(self.broadcast.clone()),
+        ))
+        .chain(
+            self.p2p
+                .iter()
+                .map(|(id, p2p)| (Recipient::One(*id), MessagePayload::Round1P2P(p2p.clone()))),
+        )
+    }
+}
+
+/// The addressed messages of round 1, see [`Round1Output::outgoing`]
+pub type Round1Messages<G> = Round1Output<G>;
+
+/// The recipient of an outgoing message
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Recipient {
+    /// Broadcast to all other participants
+    All,
+    /// Send only to the participant with this id over a private channel
+    One(usize),
+}
+
+/// The data sent by a participant in any round
+#[derive(Clone, Debug)]
+pub enum MessagePayload<G: Group + GroupEncoding + Default> {
+    /// The round 1 broadcast
+    Round1Broadcast(Round1BroadcastData<G>),
+    /// The round 1 peer-to-peer data
+    Round1P2P(Round1P2PData),
+    /// The round 2 echo broadcast
+    Round2Echo(Round2EchoBroadcastData),
+    /// The round 3 broadcast
+    Round3Broadcast(Round3BroadcastData<G>),
+    /// The round 4 echo broadcast
+    Round4Echo(Round4EchoBroadcastData<G>),
+}
+
+impl<G: Group + GroupEncoding + Default> From<Round2EchoBroadcastData> for MessagePayload<G> {
+    fn from(data: Round2EchoBroadcastData) -> Self {
+        Self::Round2Echo(data)
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> From<Round3BroadcastData<G>> for MessagePayload<G> {
+    fn from(data: Round3BroadcastData<G>) -> Self {
+        Self::Round3Broadcast(data)
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> From<Round4EchoBroadcastData<G>> for MessagePayload<G> {
+    fn from(data: Round4EchoBroadcastData<G>) -> Self {
+        Self::Round4Echo(data)
+    }
+}
+
+impl<G: Group + GroupEncoding + Default>
+    From<(Round1BroadcastData<G>, BTreeMap<usize, Round1P2PData>)> for Round1Output<G>
+{
+    fn from((broadcast, p2p): (Round1BroadcastData<G>, BTreeMap<usize, Round1P2PData>)) -> Self {
+        Self { broadcast, p2p }
+    }
+}
+
+/// The output of round 4 with everything a signing coordinator
+/// needs to build the key package of this participant
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Round4Output<G: Group + GroupEncoding + Default> {
+    /// The data to echo broadcast to all other participants
+    #[serde(bound(serialize = "Round4EchoBroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round4EchoBroadcastData<G>: Deserialize<'de>"))]
+    pub broadcast: Round4EchoBroadcastData<G>,
+    /// The id of this participant
+    pub id: usize,
+    /// The combined feldman commitments of the valid participants
+    #[serde(
+        serialize_with = "serialize_g_vec",
+        deserialize_with = "deserialize_g_vec"
+    )]
+    pub public_commitments: Vec<G>,
+    /// The public value of this participant's secret share
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub verification_share: G,
+}
+
 pub(crate) fn serialize_scalar<F: PrimeField, S: Serializer>(
     scalar: &F,
     s: S,
@@ -369,6 +995,9 @@ pub(crate) fn deserialize_scalar<'de, F: PrimeField, D: Deserializer<'de>>(
                 .decode(v.as_bytes())
                 .map_err(|_| DError::invalid_value(Unexpected::Str(v), &self))?;
             let mut repr = F::default().to_repr();
+            if bytes.len() != repr.as_ref().len() {
+                return Err(DError::invalid_length(bytes.len(), &self));
+            }
             repr.as_mut().copy_from_slice(bytes.as_slice());
             let sc = F::from_repr(repr);
             if sc.is_some().into() {
@@ -383,19 +1012,18 @@ pub(crate) fn deserialize_scalar<'de, F: PrimeField, D: Deserializer<'de>>(
             A: SeqAccess<'de>,
         {
             let mut repr = F::default().to_repr();
-            let mut i = 0;
             let len = repr.as_ref().len();
-            while let Some(b) = seq.next_element()? {
-                repr.as_mut()[i] = b;
-                i += 1;
-                if i == len {
-                    let sc = F::from_repr(repr);
-                    if sc.is_some().into() {
-                        return Ok(sc.unwrap());
-                    }
-                }
+            for i in 0..len {
+                repr.as_mut()[i] = seq
+                    .next_element()?
+                    .ok_or_else(|| DError::invalid_length(i, &self))?;
+            }
+            let sc = F::from_repr(repr);
+            if sc.is_some().into() {
+                Ok(sc.unwrap())
+            } else {
+                Err(DError::custom("unable to convert to scalar".to_string()))
             }
-            Err(DError::custom("unable to convert to scalar".to_string()))
         }
     }
 
@@ -411,6 +1039,32 @@ pub(crate) fn deserialize_scalar<'de, F: PrimeField, D: Deserializer<'de>>(
     }
 }
 
+pub(crate) fn to_bare_bytes<T: Serialize>(value: &T) -> DkgResult<Vec<u8>> {
+    serde_bare::to_vec(value).map_err(|e| Error::SerializationError(e.to_string()))
+}
+
+pub(crate) fn from_bare_bytes<T: DeserializeOwned>(bytes: &[u8]) -> DkgResult<T> {
+    serde_bare::from_slice(bytes).map_err(|e| Error::SerializationError(e.to_string()))
+}
+
+/// Decode a point from its `GroupEncoding`.
+///
+/// With the `strict-encoding` feature the point must also encode back to
+/// the same bytes so non-canonical encodings are rejected.
+pub(crate) fn decode_point<G: GroupEncoding>(repr: &G::Repr) -> Option<G> {
+    let point = Option::<G>::from(G::from_bytes(repr))?;
+    #[cfg(feature = "strict-encoding")]
+    if point.to_bytes().as_ref() != repr.as_ref() {
+        return None;
+    }
+    Some(point)
+}
+
+/// The serde default for points that are unset until configured
+pub(crate) fn default_identity<G: Group>() -> G {
+    G::identity()
+}
+
 pub(crate) fn serialize_g<G: Group + GroupEncoding + Default, S: Serializer>(
     g: &G,
     s: S,
@@ -450,13 +1104,11 @@ pub(crate) fn deserialize_g<'de, G: Group + GroupEncoding + Default, D: Deserial
             let bytes = data_encoding::BASE64URL_NOPAD
                 .decode(v.as_bytes())
                 .map_err(|_| DError::invalid_value(Unexpected::Str(v), &self))?;
-            repr.as_mut().copy_from_slice(bytes.as_slice());
-            let res = G::from_bytes(&repr);
-            if res.is_some().unwrap_u8() == 1u8 {
-                Ok(res.unwrap())
-            } else {
-                Err(DError::invalid_value(Unexpected::Str(v), &self))
+            if bytes.len() != repr.as_ref().len() {
+                return Err(DError::invalid_length(bytes.len(), &self));
             }
+            repr.as_mut().copy_from_slice(bytes.as_slice());
+            decode_point(&repr).ok_or_else(|| DError::invalid_value(Unexpected::Str(v), &self))
         }
 
         fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -470,12 +1122,7 @@ pub(crate) fn deserialize_g<'de, G: Group + GroupEncoding + Default, D: Deserial
                     .next_element()?
                     .ok_or_else(|| DError::invalid_length(input.len(), &self))?;
             }
-            let res = G::from_bytes(&repr);
-            if res.is_some().unwrap_u8() == 1u8 {
-                Ok(res.unwrap())
-            } else {
-                Err(DError::invalid_value(Unexpected::Seq, &self))
-            }
+            decode_point(&repr).ok_or_else(|| DError::invalid_value(Unexpected::Seq, &self))
         }
     }
 
@@ -494,13 +1141,14 @@ pub(crate) fn serialize_g_vec<G: Group + GroupEncoding + Default, S: Serializer>
     g: &Vec<G>,
     s: S,
 ) -> Result<S::Ok, S::Error> {
-    let v = g.iter().map(|p| p.to_bytes()).collect::<Vec<G::Repr>>();
+    // Encode one point at a time so serializing into a writer
+    // never holds the encoding of the whole vector
     if s.is_human_readable() {
-        let vv = v
-            .iter()
-            .map(|b| data_encoding::BASE64URL_NOPAD.encode(b.as_ref()))
-            .collect::<Vec<String>>();
-        vv.serialize(s)
+        let mut seq = s.serialize_seq(Some(g.len()))?;
+        for p in g {
+            seq.serialize_element(&data_encoding::BASE64URL_NOPAD.encode(p.to_bytes().as_ref()))?;
+        }
+        seq.end()
     } else {
         let size = G::Repr::default().as_ref().len();
         let uint = uint_zigzag::Uint::from(g.len());
@@ -509,8 +1157,8 @@ pub(crate) fn serialize_g_vec<G: Group + GroupEncoding + Default, S: Serializer>
         for b in &length_bytes {
             seq.serialize_element(b)?;
         }
-        for c in &v {
-            for b in c.as_ref() {
+        for p in g {
+            for b in p.to_bytes().as_ref() {
                 seq.serialize_element(b)?;
             }
         }
@@ -536,45 +1184,40 @@ pub(crate) fn deserialize_g_vec<'de, G: Group + GroupEncoding + Default, D: Dese
         where
             A: SeqAccess<'de>,
         {
+            // The length prefix is a variable number of bytes
+            // so read one byte at a time until it is complete
             let mut buffer = [0u8; Uint::MAX_BYTES];
-            let mut i = 0;
-            while let Some(b) = seq.next_element()? {
-                buffer[i] = b;
-                i += 1;
-                if i == Uint::MAX_BYTES {
+            let mut length_size = 0;
+            loop {
+                if length_size == Uint::MAX_BYTES {
+                    return Err(DError::invalid_value(Unexpected::Bytes(&buffer), &self));
+                }
+                buffer[length_size] = seq
+                    .next_element()?
+                    .ok_or_else(|| DError::invalid_length(length_size, &self))?;
+                length_size += 1;
+                if Uint::peek(&buffer[..length_size]).is_some() {
                     break;
                 }
             }
-            let bytes_cnt_size = Uint::peek(&buffer)
-                .ok_or_else(|| DError::invalid_value(Unexpected::Bytes(&buffer), &self))?;
-            let points = Uint::try_from(&buffer[..bytes_cnt_size])
-                .map_err(|_| DError::invalid_value(Unexpected::Bytes(&buffer), &self))?;
+            let points = Uint::try_from(&buffer[..length_size])
+                .map_err(|_| DError::invalid_value(Unexpected::Bytes(&buffer), &self))?
+                .0 as usize;
 
-            i = Uint::MAX_BYTES - bytes_cnt_size;
             let mut repr = G::Repr::default();
-            {
-                let r = repr.as_mut();
-                r[..i].copy_from_slice(&buffer[bytes_cnt_size..]);
-            }
             let repr_len = repr.as_ref().len();
-            let mut out = Vec::with_capacity(points.0 as usize);
-            while let Some(b) = seq.next_element()? {
-                repr.as_mut()[i] = b;
-                i += 1;
-                if i == repr_len {
-                    i = 0;
-                    let pt = G::from_bytes(&repr);
-                    if pt.is_none().unwrap_u8() == 1u8 {
-                        return Err(DError::invalid_value(Unexpected::Bytes(&buffer), &self));
-                    }
-                    out.push(pt.unwrap());
-                    if out.len() == points.0 as usize {
-                        break;
-                    }
+            // The length prefix is untrusted so don't preallocate more than a valid message needs
+            let mut out = Vec::with_capacity(points.min(MAX_PARTICIPANTS));
+            while out.len() < points {
+                for (i, r) in repr.as_mut().iter_mut().enumerate() {
+                    *r = seq
+                        .next_element()?
+                        .ok_or_else(|| DError::invalid_length(out.len() * repr_len + i, &self))?;
                 }
-            }
-            if out.len() != points.0 as usize {
-                return Err(DError::invalid_length(out.len(), &self));
+                let pt = decode_point::<G>(&repr).ok_or_else(|| {
+                    DError::invalid_value(Unexpected::Bytes(repr.as_ref()), &self)
+                })?;
+                out.push(pt);
             }
             Ok(out)
         }
@@ -588,14 +1231,16 @@ pub(crate) fn deserialize_g_vec<'de, G: Group + GroupEncoding + Default, D: Dese
             let bytes = data_encoding::BASE64URL_NOPAD
                 .decode(si.as_bytes())
                 .map_err(|_| DError::custom("unable to decode string to bytes".to_string()))?;
-            repr.as_mut().copy_from_slice(bytes.as_slice());
-            let pt = G::from_bytes(&repr);
-            if pt.is_none().unwrap_u8() == 1u8 {
-                return Err(DError::custom(
-                    "unable to convert string to point".to_string(),
+            if bytes.len() != repr.as_ref().len() {
+                return Err(DError::invalid_length(
+                    bytes.len(),
+                    &"a base64 encoded point",
                 ));
             }
-            out.push(pt.unwrap());
+            repr.as_mut().copy_from_slice(bytes.as_slice());
+            let pt = decode_point::<G>(&repr)
+                .ok_or_else(|| DError::custom("unable to convert string to point".to_string()))?;
+            out.push(pt);
         }
         Ok(out)
     } else {
@@ -605,12 +1250,35 @@ pub(crate) fn deserialize_g_vec<'de, G: Group + GroupEncoding + Default, D: Dese
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct GWrapper<G: Group + GroupEncoding + Default>(
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")] G,
+);
+
+pub(crate) fn serialize_g_map<G: Group + GroupEncoding + Default, S: Serializer>(
+    g: &BTreeMap<usize, G>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    g.iter()
+        .map(|(id, p)| (*id, GWrapper(*p)))
+        .collect::<BTreeMap<usize, GWrapper<G>>>()
+        .serialize(s)
+}
+
+pub(crate) fn deserialize_g_map<'de, G: Group + GroupEncoding + Default, D: Deserializer<'de>>(
+    d: D,
+) -> Result<BTreeMap<usize, G>, D::Error> {
+    let m = BTreeMap::<usize, GWrapper<G>>::deserialize(d)?;
+    Ok(m.into_iter().map(|(id, p)| (id, p.0)).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{complete_dkg, new_participants, round2_inputs, run_round1};
     use serde_encrypt::traits::SerdeEncryptSharedKey;
     use std::collections::BTreeMap;
-    use vsss_rs::{combine_shares, Share};
+    use vsss_rs::Share;
 
     #[test]
     fn one_corrupted_party_k256() {
@@ -634,6 +1302,12 @@ mod tests {
         one_corrupted_party::<bls12_381_plus::G2Projective>();
     }
 
+    #[test]
+    fn one_corrupted_party_pasta() {
+        one_corrupted_party::<pasta_curves::pallas::Point>();
+        one_corrupted_party::<pasta_curves::vesta::Point>();
+    }
+
     fn one_corrupted_party<G: Group + GroupEncoding + Default>() {
         const THRESHOLD: usize = 2;
         const LIMIT: usize = 4;
@@ -702,7 +1376,6 @@ mod tests {
         }
 
         let mut r4bdata = BTreeMap::new();
-        let mut r4shares = Vec::with_capacity(LIMIT);
         for p in participants.iter_mut() {
             if BAD_ID == p.get_id() {
                 continue;
@@ -710,9 +1383,7 @@ mod tests {
             let res = p.round4(&r3bdata);
             assert!(res.is_ok());
             let bdata = res.unwrap();
-            let share = p.get_secret_share().unwrap();
             r4bdata.insert(p.get_id(), bdata);
-            r4shares.push(<Vec<u8> as Share>::from_field_element(p.get_id() as u8, share).unwrap());
             assert!(p.round4(&r3bdata).is_err());
         }
 
@@ -723,9 +1394,13 @@ mod tests {
             assert!(p.round5(&r4bdata).is_ok());
         }
 
-        let res = combine_shares::<G::Scalar, u8, Vec<u8>>(&r4shares);
+        let completed = participants.iter().collect::<Vec<_>>();
+        let res = test_util::reconstruct_secret(&completed, &[1, 2, 3]);
         assert!(res.is_ok());
         let secret = res.unwrap();
+        assert!(test_util::reconstruct_secret(&completed, &[1]).is_err());
+        assert!(test_util::reconstruct_secret(&completed, &[1, 4]).is_err());
+        assert!(test_util::reconstruct_secret(&completed, &[1, 1]).is_err());
 
         assert_eq!(r4bdata[&1].public_key, G::generator() * secret);
     }
@@ -757,6 +1432,12 @@ mod tests {
         serialization_curve::<vsss_rs::curve25519::WrappedEdwards>();
     }
 
+    #[test]
+    fn serialization_pasta() {
+        serialization_curve::<pasta_curves::pallas::Point>();
+        serialization_curve::<pasta_curves::vesta::Point>();
+    }
+
     fn serialization_curve<G: Group + GroupEncoding + Default>() {
         const THRESHOLD: usize = 2;
         const LIMIT: usize = 3;
@@ -781,6 +1462,7 @@ mod tests {
             let res = serde_json::from_str::<Round1BroadcastData<G>>(&json);
             assert!(res.is_ok());
             let bdata2 = res.unwrap();
+            assert_eq!(bdata, bdata2);
             assert_eq!(bdata.message_generator, bdata.message_generator);
             assert_eq!(bdata.blinder_generator, bdata2.blinder_generator);
             assert_eq!(
@@ -796,6 +1478,7 @@ mod tests {
             let res = serde_json::from_str::<BTreeMap<usize, Round1P2PData>>(&json);
             assert!(res.is_ok());
             let pdata2 = res.unwrap();
+            assert_eq!(pdata, pdata2);
             assert_eq!(pdata.len(), pdata2.len());
             for (id, val) in &pdata {
                 assert!(pdata2.contains_key(id));
@@ -808,6 +1491,7 @@ mod tests {
             let res = serde_bare::from_slice::<Round1BroadcastData<G>>(&bin);
             assert!(res.is_ok());
             let bdata2 = res.unwrap();
+            assert_eq!(bdata, bdata2);
             assert_eq!(bdata.message_generator, bdata.message_generator);
             assert_eq!(bdata.blinder_generator, bdata2.blinder_generator);
             assert_eq!(
@@ -823,6 +1507,7 @@ mod tests {
             let res = serde_bare::from_slice::<BTreeMap<usize, Round1P2PData>>(&bin);
             assert!(res.is_ok());
             let pdata2 = res.unwrap();
+            assert_eq!(pdata, pdata2);
             assert_eq!(pdata.len(), pdata2.len());
             for (id, val) in &pdata {
                 assert!(pdata2.contains_key(id));
@@ -835,6 +1520,7 @@ mod tests {
             let res = Round1BroadcastData::<G>::decrypt_owned(&bin, &shared_key);
             assert!(res.is_ok());
             let bdata2 = res.unwrap();
+            assert_eq!(bdata, bdata2);
             assert_eq!(bdata.message_generator, bdata.message_generator);
             assert_eq!(bdata.blinder_generator, bdata2.blinder_generator);
             assert_eq!(
@@ -901,6 +1587,7 @@ mod tests {
         let res = serde_json::from_str::<BTreeMap<usize, Round2EchoBroadcastData>>(&json);
         assert!(res.is_ok());
         let r2bdata2 = res.unwrap();
+        assert_eq!(r2bdata, r2bdata2);
         assert_eq!(
             r2bdata[&1].valid_participant_ids,
             r2bdata2[&1].valid_participant_ids
@@ -910,6 +1597,7 @@ mod tests {
         let res = serde_bare::from_slice::<BTreeMap<usize, Round2EchoBroadcastData>>(&bin);
         assert!(res.is_ok());
         let r2bdata2 = res.unwrap();
+        assert_eq!(r2bdata, r2bdata2);
         assert_eq!(
             r2bdata[&1].valid_participant_ids,
             r2bdata2[&1].valid_participant_ids
@@ -936,6 +1624,7 @@ mod tests {
         let res = serde_json::from_str::<BTreeMap<usize, Round3BroadcastData<G>>>(&json);
         assert!(res.is_ok());
         let r3bdata2 = res.unwrap();
+        assert_eq!(r3bdata, r3bdata2);
         assert_eq!(
             r3bdata.get(&1).unwrap().commitments,
             r3bdata2.get(&1).unwrap().commitments
@@ -953,6 +1642,7 @@ mod tests {
         let res = serde_bare::from_slice::<BTreeMap<usize, Round3BroadcastData<G>>>(&bin);
         assert!(res.is_ok());
         let r3bdata2 = res.unwrap();
+        assert_eq!(r3bdata, r3bdata2);
         assert_eq!(
             r3bdata.get(&1).unwrap().commitments,
             r3bdata2.get(&1).unwrap().commitments
@@ -1005,4 +1695,892 @@ mod tests {
             r4bdata2.get(&3).unwrap().public_key
         );
     }
+
+    #[test]
+    fn schema_matches_json() {
+        fn field_names<T: Serialize>(value: &T) -> Vec<String> {
+            let json = serde_json::to_value(value).unwrap();
+            let mut names = json
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        }
+        fn schema_names(schema: &[FieldSchema]) -> Vec<String> {
+            let mut names = schema
+                .iter()
+                .map(|f| f.name.to_string())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        }
+
+        let parameters = Parameters::<k256::ProjectivePoint>::new(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(2).unwrap(),
+        );
+        let mut participants = [
+            SecretParticipant::<k256::ProjectivePoint>::new(
+                NonZeroUsize::new(1).unwrap(),
+                parameters,
+            )
+            .unwrap(),
+            SecretParticipant::<k256::ProjectivePoint>::new(
+                NonZeroUsize::new(2).unwrap(),
+                parameters,
+            )
+            .unwrap(),
+        ];
+        let (b1, p1) = participants[0].round1().unwrap();
+        let (b2, p2) = participants[1].round1().unwrap();
+        assert_eq!(
+            field_names(&b1),
+            schema_names(Round1BroadcastData::<k256::ProjectivePoint>::schema())
+        );
+        assert_eq!(field_names(&p1[&2]), schema_names(Round1P2PData::schema()));
+
+        let e1 = participants[0]
+            .round2(
+                maplit::btreemap! { 2 => b2 },
+                maplit::btreemap! { 2 => p2[&1].clone() },
+            )
+            .unwrap();
+        let e2 = participants[1]
+            .round2(
+                maplit::btreemap! { 1 => b1 },
+                maplit::btreemap! { 1 => p1[&2].clone() },
+            )
+            .unwrap();
+        assert_eq!(
+            field_names(&e1),
+            schema_names(Round2EchoBroadcastData::schema())
+        );
+
+        let echoes = maplit::btreemap! { 1 => e1, 2 => e2 };
+        let r3 = participants[0].round3(&echoes).unwrap();
+        assert_eq!(
+            field_names(&r3),
+            schema_names(Round3BroadcastData::<k256::ProjectivePoint>::schema())
+        );
+        let r3_2 = participants[1].round3(&echoes).unwrap();
+
+        let r4 = participants[0]
+            .round4(&maplit::btreemap! { 1 => r3, 2 => r3_2 })
+            .unwrap();
+        assert_eq!(
+            field_names(&r4),
+            schema_names(Round4EchoBroadcastData::<k256::ProjectivePoint>::schema())
+        );
+    }
+
+    #[test]
+    fn participant_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SecretParticipant<k256::ProjectivePoint>>();
+        assert_send_sync::<SecretParticipant<k256::ProjectivePoint, InMemoryStore>>();
+    }
+
+    #[test]
+    fn round2_echo_broadcast_data_accessors() {
+        let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+        let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+        let (bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+        let echo = participants[0].round2(bdata, p2p).unwrap();
+        assert_eq!(echo.valid_participant_ids(), &maplit::btreeset! { 1, 2, 3 });
+
+        let echo = Round2EchoBroadcastData::new(maplit::btreeset! { 1, 3 });
+        assert_eq!(echo.valid_participant_ids(), &maplit::btreeset! { 1, 3 });
+        let json = serde_json::to_string(&echo).unwrap();
+        assert_eq!(json, r#"{"valid_participant_ids":[1,3]}"#);
+    }
+
+    #[test]
+    fn parameters_new_deterministic() {
+        fn check<G: Group + GroupEncoding + Default>() {
+            let threshold = NonZeroUsize::new(2).unwrap();
+            let limit = NonZeroUsize::new(3).unwrap();
+            let a = Parameters::<G>::new(threshold, limit);
+            let b = Parameters::<G>::new(threshold, limit);
+            assert_eq!(a.blinder_generator, b.blinder_generator);
+            assert_eq!(a.message_generator, G::generator());
+            assert_ne!(a.blinder_generator, a.message_generator);
+            assert!(!bool::from(a.blinder_generator.is_identity()));
+        }
+        check::<k256::ProjectivePoint>();
+        check::<bls12_381_plus::G1Projective>();
+        check::<bls12_381_plus::G2Projective>();
+        check::<pasta_curves::pallas::Point>();
+    }
+
+    #[test]
+    fn serialize_many_commitments() {
+        // 200 needs a two byte length prefix
+        let commitments = (1..=200u64)
+            .map(|i| k256::ProjectivePoint::GENERATOR * k256::Scalar::from(i))
+            .collect::<Vec<_>>();
+        assert_eq!(Uint::from(commitments.len()).to_vec().len(), 2);
+        let data = Round3BroadcastData {
+            commitments: commitments.clone(),
+            pedersen_commitments: Vec::new(),
+            verification_share: k256::ProjectivePoint::GENERATOR,
+        };
+
+        let bytes = serde_bare::to_vec(&data).unwrap();
+        let res = serde_bare::from_slice::<Round3BroadcastData<k256::ProjectivePoint>>(&bytes);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(res.commitments, commitments);
+        assert!(res.pedersen_commitments.is_empty());
+
+        let json = serde_json::to_string(&data).unwrap();
+        let res = serde_json::from_str::<Round3BroadcastData<k256::ProjectivePoint>>(&json);
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().commitments, commitments);
+
+        // Truncated input must fail instead of panicking
+        let res = serde_bare::from_slice::<Round3BroadcastData<k256::ProjectivePoint>>(
+            &bytes[..bytes.len() / 2],
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parameters_validate_against() {
+        type G = k256::ProjectivePoint;
+        let two = NonZeroUsize::new(2).unwrap();
+        let three = NonZeroUsize::new(3).unwrap();
+        let parameters = Parameters::<G>::new(two, three);
+        assert!(parameters.validate_against(&parameters).is_ok());
+
+        let mismatched = [
+            (Parameters::<G>::new(three, three), "threshold"),
+            (Parameters::<G>::new(two, two), "limit"),
+            (
+                Parameters::with_generators(
+                    two,
+                    three,
+                    G::GENERATOR.double(),
+                    parameters.blinder_generator,
+                ),
+                "message_generator",
+            ),
+            (
+                Parameters::with_generators(two, three, G::GENERATOR, G::GENERATOR.double()),
+                "blinder_generator",
+            ),
+        ];
+        for (other, name) in mismatched {
+            assert!(matches!(
+                parameters.validate_against(&other),
+                Err(Error::ParametersMismatch { field }) if field == name
+            ));
+        }
+
+        let participant = SecretParticipant::<G>::new(two, parameters).unwrap();
+        assert!(participant
+            .get_parameters()
+            .validate_against(&parameters)
+            .is_ok());
+    }
+
+    #[test]
+    fn verify_share_standalone() {
+        type G = k256::ProjectivePoint;
+        let mut participants = new_participants::<G>(2, 3);
+        let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+        let bdata = &r1bdata[&1];
+        let p2p = &r1p2pdata[&1][&2];
+
+        let check = |share: &[u8], blind_share: &[u8], id: usize| {
+            verify_share(
+                share,
+                blind_share,
+                &bdata.pedersen_commitments,
+                id,
+                bdata.message_generator,
+                bdata.blinder_generator,
+            )
+        };
+        assert!(check(&p2p.secret_share, &p2p.blind_share, 2));
+        // Wrong recipient
+        assert!(!check(&p2p.secret_share, &p2p.blind_share, 3));
+        // Swapped shares
+        assert!(!check(&p2p.blind_share, &p2p.secret_share, 2));
+        // Share for another participant
+        let other = &r1p2pdata[&1][&3];
+        assert!(!check(&other.secret_share, &p2p.blind_share, 2));
+        assert!(!check(&[], &p2p.blind_share, 2));
+    }
+
+    #[test]
+    fn lagrange_coefficients_match_shares() {
+        type G = k256::ProjectivePoint;
+        let mut participants = new_participants::<G>(3, 5);
+        complete_dkg(&mut participants);
+
+        let ids = [2, 4, 5];
+        let coefficients = lagrange_coefficients::<G>(&ids).unwrap();
+        let secret = ids
+            .iter()
+            .map(|id| participants[id - 1].get_secret_share().unwrap() * coefficients[id])
+            .sum::<k256::Scalar>();
+        assert_eq!(
+            participants[0].get_public_key(),
+            Some(G::GENERATOR * secret)
+        );
+
+        assert!(lagrange_coefficients::<G>(&[]).is_err());
+        assert!(lagrange_coefficients::<G>(&[0, 1]).is_err());
+        assert!(lagrange_coefficients::<G>(&[1, 1]).is_err());
+    }
+
+    #[test]
+    fn verify_public_key_from_broadcasts() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| {
+                SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+            r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let public_key = participants[0].round4(&r3bdata).unwrap().public_key;
+
+        assert!(verify_public_key(&parameters, &r3bdata, public_key).unwrap());
+        assert!(!verify_public_key(&parameters, &r3bdata, G::GENERATOR).unwrap());
+
+        let mut bad = r3bdata.clone();
+        bad.get_mut(&2).unwrap().commitments[1] = G::IDENTITY;
+        assert!(matches!(
+            verify_public_key(&parameters, &bad, public_key),
+            Err(Error::InvalidCommitment { from: 2 })
+        ));
+        bad.remove(&2);
+        bad.remove(&3);
+        assert!(verify_public_key(&parameters, &bad, public_key).is_err());
+    }
+
+    #[test]
+    fn parameters_with_seed() {
+        struct ZeroHasher;
+        impl DkgHasher for ZeroHasher {
+            fn digest(_inputs: &[&[u8]]) -> [u8; 32] {
+                [0u8; 32]
+            }
+        }
+
+        type G = k256::ProjectivePoint;
+        let two = NonZeroUsize::new(2).unwrap();
+        let three = NonZeroUsize::new(3).unwrap();
+        let a = Parameters::<G>::with_seed::<Sha256Hasher>(two, three, b"session 1");
+        let b = Parameters::<G>::with_seed::<Sha256Hasher>(two, three, b"session 1");
+        let c = Parameters::<G>::with_seed::<Sha256Hasher>(two, three, b"session 2");
+        assert!(a.validate_against(&b).is_ok());
+        assert_ne!(a.blinder_generator, c.blinder_generator);
+        assert_ne!(
+            a.blinder_generator,
+            Parameters::<G>::new(two, three).blinder_generator
+        );
+
+        let z1 = Parameters::<G>::with_seed::<ZeroHasher>(two, three, b"session 1");
+        let z2 = Parameters::<G>::with_seed::<ZeroHasher>(two, three, b"session 2");
+        assert_eq!(z1.blinder_generator, z2.blinder_generator);
+        assert_eq!(z1.fingerprint_with::<ZeroHasher>(), [0u8; 32]);
+
+        // The builder derives the session blinder generator with its hasher
+        let session = |id: &[u8]| {
+            ParametersBuilder::<G, ZeroHasher>::with_hasher()
+                .threshold(2)
+                .limit(3)
+                .session_id(id)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            session(b"one").blinder_generator,
+            session(b"two").blinder_generator
+        );
+        let sha256 = ParametersBuilder::<G>::new()
+            .threshold(2)
+            .limit(3)
+            .session_id(b"one")
+            .build()
+            .unwrap();
+        assert_ne!(sha256.blinder_generator, session(b"one").blinder_generator);
+    }
+
+    #[test]
+    fn max_participants() {
+        type G = k256::ProjectivePoint;
+        let id = NonZeroUsize::new(1).unwrap();
+        let threshold = NonZeroUsize::new(2).unwrap();
+        let parameters =
+            Parameters::<G>::new(threshold, NonZeroUsize::new(MAX_PARTICIPANTS + 1).unwrap());
+        assert!(matches!(
+            SecretParticipant::<G>::new(id, parameters),
+            Err(Error::InitializationError(_))
+        ));
+        let parameters =
+            Parameters::<G>::new(threshold, NonZeroUsize::new(MAX_PARTICIPANTS).unwrap());
+        assert!(SecretParticipant::<G>::new(id, parameters).is_ok());
+    }
+
+    #[test]
+    fn golden_vectors_k256() {
+        // The fixtures are participant 1's messages for the polynomials below,
+        // computed independently of this crate. If this fails the wire format changed.
+        type G = k256::ProjectivePoint;
+        let scalar = |i: u64| k256::Scalar::from(i);
+        let parameters = Parameters::<G>::with_generators(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(3).unwrap(),
+            G::GENERATOR,
+            G::GENERATOR * scalar(7),
+        );
+        let mut participants = (1..=3u64)
+            .map(|i| {
+                SecretParticipant::<G>::from_polynomials(
+                    NonZeroUsize::new(i as usize).unwrap(),
+                    parameters,
+                    vec![scalar(i), scalar(i + 10)],
+                    vec![scalar(i + 20), scalar(i + 30)],
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+            r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let r4bdata = participants[0].round4(&r3bdata).unwrap();
+
+        fn check<T: Serialize + serde::de::DeserializeOwned>(
+            value: &T,
+            json: &str,
+            hex: Option<&str>,
+        ) -> (T, Option<T>) {
+            assert_eq!(serde_json::to_string(value).unwrap(), json.trim());
+            let from_json = serde_json::from_str::<T>(json).unwrap();
+            let from_bare = hex.map(|hex| {
+                let bytes = data_encoding::HEXLOWER
+                    .decode(hex.trim().as_bytes())
+                    .unwrap();
+                assert_eq!(serde_bare::to_vec(value).unwrap(), bytes);
+                serde_bare::from_slice::<T>(&bytes).unwrap()
+            });
+            (from_json, from_bare)
+        }
+
+        // The echo key is random
+        #[cfg(not(feature = "signed-echo"))]
+        {
+            let (a, b) = check(
+                &r1bdata[&1],
+                include_str!("../tests/fixtures/k256/round1_broadcast.json"),
+                Some(include_str!("../tests/fixtures/k256/round1_broadcast.hex")),
+            );
+            for r in [a, b.unwrap()] {
+                assert_eq!(r.message_generator, G::GENERATOR);
+                assert_eq!(r.blinder_generator, G::GENERATOR * scalar(7));
+                assert_eq!(
+                    r.pedersen_commitments,
+                    vec![G::GENERATOR * scalar(148), G::GENERATOR * scalar(228)]
+                );
+            }
+        }
+
+        let (a, b) = check(
+            &r1p2pdata[&1][&2],
+            include_str!("../tests/fixtures/k256/round1_p2p.json"),
+            Some(include_str!("../tests/fixtures/k256/round1_p2p.hex")),
+        );
+        for r in [a, b.unwrap()] {
+            assert_eq!(r.secret_share.identifier(), 2);
+            assert_eq!(r.blind_share.identifier(), 2);
+            assert_eq!(
+                r.secret_share.as_field_element::<k256::Scalar>().unwrap(),
+                scalar(23)
+            );
+            assert_eq!(
+                r.blind_share.as_field_element::<k256::Scalar>().unwrap(),
+                scalar(83)
+            );
+        }
+
+        // Signed echoes carry a random signature
+        #[cfg(not(feature = "signed-echo"))]
+        {
+            let (a, _) = check(
+                &r2bdata[&1],
+                include_str!("../tests/fixtures/k256/round2_echo.json"),
+                None,
+            );
+            assert_eq!(a.valid_participant_ids, maplit::btreeset! { 1, 2, 3 });
+        }
+
+        let (a, b) = check(
+            &r3bdata[&1],
+            include_str!("../tests/fixtures/k256/round3_broadcast.json"),
+            Some(include_str!("../tests/fixtures/k256/round3_broadcast.hex")),
+        );
+        for r in [a, b.unwrap()] {
+            assert_eq!(r.commitments, vec![G::GENERATOR, G::GENERATOR * scalar(11)]);
+            assert_eq!(
+                r.pedersen_commitments,
+                vec![G::GENERATOR * scalar(148), G::GENERATOR * scalar(228)]
+            );
+            // f(x) = 6 + 36x so participant 1 holds 42
+            assert_eq!(r.verification_share, G::GENERATOR * scalar(42));
+        }
+
+        let (a, b) = check(
+            &r4bdata,
+            include_str!("../tests/fixtures/k256/round4_echo.json"),
+            Some(include_str!("../tests/fixtures/k256/round4_echo.hex")),
+        );
+        for r in [a, b.unwrap()] {
+            assert_eq!(r.public_key, G::GENERATOR * scalar(6));
+        }
+    }
+
+    #[test]
+    fn documented_json_examples() {
+        // The JSON examples in the docs above are the k256 golden fixtures
+        type G = k256::ProjectivePoint;
+        let mut examples = BTreeMap::new();
+        let mut example = None;
+        let mut finished = None;
+        for line in include_str!("lib.rs").lines().map(str::trim) {
+            if line == "/// ```json" {
+                example = Some(String::new());
+            } else if line == "/// ```" {
+                finished = example.take();
+            } else if let Some(json) = example.as_mut() {
+                json.push_str(line.trim_start_matches("///"));
+            } else if let Some(name) = line.strip_prefix("pub struct ") {
+                if let Some(json) = finished.take() {
+                    let name = name.split(|c: char| !c.is_alphanumeric()).next().unwrap();
+                    examples.insert(name, json);
+                }
+            }
+        }
+
+        fn check<T: Serialize + serde::de::DeserializeOwned>(example: &str, fixture: &str) {
+            let value = serde_json::from_str::<T>(example).unwrap();
+            assert_eq!(serde_json::to_string(&value).unwrap(), fixture.trim());
+        }
+        #[cfg(not(feature = "signed-echo"))]
+        check::<Round1BroadcastData<G>>(
+            &examples["Round1BroadcastData"],
+            include_str!("../tests/fixtures/k256/round1_broadcast.json"),
+        );
+        check::<Round1P2PData>(
+            &examples["Round1P2PData"],
+            include_str!("../tests/fixtures/k256/round1_p2p.json"),
+        );
+        #[cfg(not(feature = "signed-echo"))]
+        check::<Round2EchoBroadcastData>(
+            &examples["Round2EchoBroadcastData"],
+            include_str!("../tests/fixtures/k256/round2_echo.json"),
+        );
+        check::<Round3BroadcastData<G>>(
+            &examples["Round3BroadcastData"],
+            include_str!("../tests/fixtures/k256/round3_broadcast.json"),
+        );
+        check::<Round4EchoBroadcastData<G>>(
+            &examples["Round4EchoBroadcastData"],
+            include_str!("../tests/fixtures/k256/round4_echo.json"),
+        );
+    }
+
+    #[test]
+    fn error_is_clone_and_std_error() {
+        fn boxed(e: Error) -> Box<dyn std::error::Error + Send + Sync> {
+            Box::new(e)
+        }
+
+        let err = Error::from(std::io::Error::other("disk full"));
+        assert_eq!(err.clone().to_string(), "io error: disk full");
+
+        let err = Error::from(std::fmt::Error);
+        assert!(boxed(err.clone()).source().is_some());
+
+        let err = Error::InsufficientParticipants { have: 1, need: 2 };
+        assert_eq!(boxed(err.clone()).to_string(), err.to_string());
+    }
+
+    #[test]
+    fn evaluate_public_polynomial_matches_shares() {
+        type G = k256::ProjectivePoint;
+        let mut participants = new_participants::<G>(3, 5);
+        complete_dkg(&mut participants);
+        let commitments = participants[0].get_public_commitments().to_vec();
+        assert_eq!(evaluate_public_polynomial(&commitments, 0), commitments[0]);
+        for p in &participants {
+            let share = p.get_secret_share().unwrap();
+            assert_eq!(
+                evaluate_public_polynomial(&commitments, p.get_id()),
+                G::GENERATOR * share
+            );
+        }
+    }
+
+    #[test]
+    fn schnorr_signature() {
+        type G = p256::ProjectivePoint;
+        let secret_key = <G as Group>::Scalar::from(1234u64);
+        let public_key = G::GENERATOR * secret_key;
+        let signature = SchnorrSignature::<G>::sign(&secret_key, b"message", rand_core::OsRng);
+        assert!(signature.verify(public_key, b"message"));
+        assert!(!signature.verify(public_key, b"other message"));
+        assert!(!signature.verify(G::GENERATOR, b"message"));
+
+        let json = serde_json::to_string(&signature).unwrap();
+        let signature2 = serde_json::from_str::<SchnorrSignature<G>>(&json).unwrap();
+        assert!(signature2.verify(public_key, b"message"));
+    }
+
+    #[test]
+    fn from_bytes_never_panics() {
+        type G = k256::ProjectivePoint;
+        let mut participants = new_participants::<G>(2, 3);
+        let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+        let encodings = [
+            r1bdata[&1].to_bytes().unwrap(),
+            r1p2pdata[&1][&2].to_bytes().unwrap(),
+            Round2EchoBroadcastData::new(maplit::btreeset! {1, 2, 3})
+                .to_bytes()
+                .unwrap(),
+        ];
+        let data = Round1BroadcastData::<G>::from_bytes(&encodings[0]).unwrap();
+        assert_eq!(data.pedersen_commitments, r1bdata[&1].pedersen_commitments);
+        assert!(Round1P2PData::from_bytes(&encodings[1]).is_ok());
+
+        let parse_all = |bytes: &[u8]| {
+            let _ = Round1BroadcastData::<G>::from_bytes(bytes);
+            let _ = Round1P2PData::from_bytes(bytes);
+            let _ = Round2EchoBroadcastData::from_bytes(bytes);
+            let _ = Round3BroadcastData::<G>::from_bytes(bytes);
+            let _ = Round4EchoBroadcastData::<G>::from_bytes(bytes);
+        };
+        for bytes in &encodings {
+            for len in 0..bytes.len() {
+                parse_all(&bytes[..len]);
+            }
+            assert!(Round1BroadcastData::<G>::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        }
+        // A huge length prefix must not be trusted
+        parse_all(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+
+        let mut rng = rand_chacha::ChaChaRng::from_seed([3u8; 32]);
+        for len in 0..512 {
+            let mut bytes = vec![0u8; len];
+            rand_core::RngCore::fill_bytes(&mut rng, &mut bytes);
+            parse_all(&bytes);
+        }
+    }
+
+    #[test]
+    fn wrong_length_base64() {
+        type G = k256::ProjectivePoint;
+        let point = data_encoding::BASE64URL_NOPAD.encode(&G::GENERATOR.to_bytes());
+        let truncated = data_encoding::BASE64URL_NOPAD.encode(&G::GENERATOR.to_bytes()[..20]);
+        let mut overlong = G::GENERATOR.to_bytes().to_vec();
+        overlong.push(0);
+        let overlong = data_encoding::BASE64URL_NOPAD.encode(&overlong);
+
+        let echo = |p: &str| format!(r#"{{"public_key":"{}"}}"#, p);
+        assert!(serde_json::from_str::<Round4EchoBroadcastData<G>>(&echo(&point)).is_ok());
+        assert!(serde_json::from_str::<Round4EchoBroadcastData<G>>(&echo(&truncated)).is_err());
+        assert!(serde_json::from_str::<Round4EchoBroadcastData<G>>(&echo(&overlong)).is_err());
+        assert!(serde_json::from_str::<Round4EchoBroadcastData<G>>(&echo("")).is_err());
+
+        let commitments = |p: &str| {
+            format!(
+                r#"{{"commitments":["{}","{}"],"pedersen_commitments":["{}"]}}"#,
+                point, p, point
+            )
+        };
+        assert!(serde_json::from_str::<Round3BroadcastData<G>>(&commitments(&point)).is_ok());
+        assert!(serde_json::from_str::<Round3BroadcastData<G>>(&commitments(&truncated)).is_err());
+        assert!(serde_json::from_str::<Round3BroadcastData<G>>(&commitments(&overlong)).is_err());
+
+        let scalar = k256::Scalar::from(5u64).to_repr();
+        let package = |s: &str| {
+            format!(
+                r#"{{"identifier":1,"secret_share":"{}","public_key":"{}","verification_shares":{{}}}}"#,
+                s, point
+            )
+        };
+        let valid = data_encoding::BASE64URL_NOPAD.encode(&scalar);
+        let truncated = data_encoding::BASE64URL_NOPAD.encode(&scalar[..31]);
+        let mut overlong = scalar.to_vec();
+        overlong.push(0);
+        let overlong = data_encoding::BASE64URL_NOPAD.encode(&overlong);
+        assert!(serde_json::from_str::<KeyPackage<G>>(&package(&valid)).is_ok());
+        assert!(serde_json::from_str::<KeyPackage<G>>(&package(&truncated)).is_err());
+        assert!(serde_json::from_str::<KeyPackage<G>>(&package(&overlong)).is_err());
+    }
+
+    #[test]
+    fn parameters_builder() {
+        type G = k256::ProjectivePoint;
+        let threshold = NonZeroUsize::new(2).unwrap();
+        let limit = NonZeroUsize::new(3).unwrap();
+
+        let parameters = ParametersBuilder::<G>::new()
+            .threshold(2)
+            .limit(3)
+            .build()
+            .unwrap();
+        assert!(parameters
+            .validate_against(&Parameters::new(threshold, limit))
+            .is_ok());
+
+        let parameters = ParametersBuilder::<G>::new()
+            .threshold(2)
+            .limit(3)
+            .blinder_generator(G::GENERATOR * k256::Scalar::from(7u64))
+            .build()
+            .unwrap();
+        assert!(parameters
+            .validate_against(&Parameters::with_generators(
+                threshold,
+                limit,
+                G::GENERATOR,
+                G::GENERATOR * k256::Scalar::from(7u64)
+            ))
+            .is_ok());
+
+        let session = |id: &[u8]| {
+            ParametersBuilder::<G>::new()
+                .threshold(2)
+                .limit(3)
+                .session_id(id)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            session(b"one").blinder_generator,
+            session(b"one").blinder_generator
+        );
+        assert_ne!(
+            session(b"one").blinder_generator,
+            session(b"two").blinder_generator
+        );
+
+        let builder = ParametersBuilder::<G>::new().threshold(2).limit(3);
+        assert!(ParametersBuilder::<G>::new().threshold(2).build().is_err());
+        assert!(ParametersBuilder::<G>::new().limit(3).build().is_err());
+        assert!(builder.threshold(4).build().is_err());
+        assert!(builder.threshold(0).build().is_err());
+        assert!(builder.threshold(1).build().is_err());
+        assert!(builder.limit(MAX_PARTICIPANTS + 1).build().is_err());
+        assert!(builder.message_generator(G::IDENTITY).build().is_err());
+        assert!(builder.blinder_generator(G::GENERATOR).build().is_err());
+        assert!(builder
+            .blinder_generator(G::GENERATOR.double())
+            .session_id(b"one")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn share_encoding_round_trip() {
+        share_encoding_round_trip_curve::<k256::ProjectivePoint>();
+        share_encoding_round_trip_curve::<p256::ProjectivePoint>();
+        share_encoding_round_trip_curve::<vsss_rs::curve25519::WrappedRistretto>();
+    }
+
+    fn share_encoding_round_trip_curve<G: Group + GroupEncoding + Default>() {
+        let mut participants = new_participants::<G>(2, 3);
+        let (_, r1p2pdata) = run_round1(&mut participants);
+        for (from, p2p) in &r1p2pdata {
+            for (to, data) in p2p {
+                let json = serde_json::to_string(data).unwrap();
+                let bin = data.to_bytes().unwrap();
+                for data in [
+                    serde_json::from_str::<Round1P2PData>(&json).unwrap(),
+                    Round1P2PData::from_bytes(&bin).unwrap(),
+                ] {
+                    let (id, value) = decode_share::<G::Scalar>(&data.secret_share).unwrap();
+                    assert_eq!(id, *to, "share from {} lost its id", from);
+                    assert_eq!(data.secret_share.identifier() as usize, *to);
+                    assert_eq!(encode_share(id, value).unwrap(), data.secret_share);
+                    assert_eq!(decode_share::<G::Scalar>(&data.blind_share).unwrap().0, *to);
+                }
+            }
+        }
+
+        let value = G::Scalar::from(7u64);
+        assert!(encode_share(0, value).is_err());
+        assert!(encode_share(MAX_PARTICIPANTS + 1, value).is_err());
+        let mut share = encode_share(MAX_PARTICIPANTS, value).unwrap();
+        assert_eq!(
+            decode_share::<G::Scalar>(&share).unwrap(),
+            (MAX_PARTICIPANTS, value)
+        );
+        share[0] = 0;
+        assert!(decode_share::<G::Scalar>(&share).is_err());
+        assert!(decode_share::<G::Scalar>(&share[1..]).is_err());
+    }
+
+    #[test]
+    fn parameters_bytes() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let bytes = parameters.to_bytes();
+        // Two single byte varints and two compressed points
+        assert_eq!(bytes.len(), 2 + 2 * 33);
+        assert!(bytes.len() < serde_json::to_string(&parameters).unwrap().len());
+        let decoded = Parameters::<G>::from_bytes(&bytes).unwrap();
+        parameters.validate_against(&decoded).unwrap();
+
+        let coordinated = parameters.with_coordinator(G::GENERATOR * k256::Scalar::from(42u64));
+        let decoded = Parameters::<G>::from_bytes(&coordinated.to_bytes()).unwrap();
+        coordinated.validate_against(&decoded).unwrap();
+        assert_eq!(decoded.coordinator(), coordinated.coordinator());
+
+        for len in 0..bytes.len() {
+            assert!(Parameters::<G>::from_bytes(&bytes[..len]).is_err());
+        }
+        let mut long = coordinated.to_bytes();
+        long.push(0);
+        assert!(Parameters::<G>::from_bytes(&long).is_err());
+    }
+
+    #[cfg(feature = "strict-encoding")]
+    #[test]
+    fn strict_encoding_rejects_non_canonical_points() {
+        type G = vsss_rs::curve25519::WrappedEdwards;
+        // y = p + 1 is the non-canonical encoding of the identity's y = 1
+        let mut bytes = [0xffu8; 32];
+        bytes[0] = 0xee;
+        bytes[31] = 0x7f;
+        assert!(serde_bare::from_slice::<GWrapper<G>>(&bytes).is_err());
+        let mut repr = <G as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&bytes);
+        assert!(decode_point::<G>(&repr).is_none());
+
+        let point = G::generator();
+        assert_eq!(decode_point::<G>(&point.to_bytes()), Some(point));
+    }
+
+    #[test]
+    fn rng_failure() {
+        struct FailingRng;
+
+        impl rand_core::RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 {
+                unreachable!()
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                unreachable!()
+            }
+
+            fn fill_bytes(&mut self, _dest: &mut [u8]) {
+                unreachable!()
+            }
+
+            fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                Err(rand_core::Error::new("entropy unavailable"))
+            }
+        }
+
+        assert!(matches!(
+            participant::seeded_rng(FailingRng),
+            Err(Error::RngFailure(_))
+        ));
+        assert!(participant::entropy_rng().is_ok());
+    }
+
+    #[test]
+    fn blinder_generator_subgroup_check() {
+        type G = vsss_rs::curve25519::WrappedEdwards;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        assert!(in_prime_order_subgroup(parameters.blinder_generator));
+        assert!(in_prime_order_subgroup(G::generator()));
+
+        // The point (0, -1) has order 2
+        let mut repr = <G as GroupEncoding>::Repr::default();
+        repr.as_mut()[0] = 0xec;
+        for b in &mut repr.as_mut()[1..31] {
+            *b = 0xff;
+        }
+        repr.as_mut()[31] = 0x7f;
+        let torsion = Option::<G>::from(G::from_bytes(&repr)).unwrap();
+        assert!(!in_prime_order_subgroup(torsion));
+
+        let blinder_generator = parameters.blinder_generator + torsion;
+        assert!(!in_prime_order_subgroup(blinder_generator));
+        let parameters = Parameters::with_generators(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(3).unwrap(),
+            G::generator(),
+            blinder_generator,
+        );
+        assert!(matches!(
+            SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters),
+            Err(Error::InitializationError(_))
+        ));
+        assert!(ParametersBuilder::<G>::new()
+            .threshold(2)
+            .limit(3)
+            .blinder_generator(blinder_generator)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn parameters_from_fraction() {
+        type G = k256::ProjectivePoint;
+        let parameters = Parameters::<G>::from_fraction(2, 3, 9).unwrap();
+        assert_eq!(parameters.threshold, 6);
+        assert_eq!(parameters.limit, 9);
+        assert_eq!(
+            Parameters::<G>::from_fraction(2, 3, 10).unwrap().threshold,
+            7
+        );
+        assert_eq!(
+            Parameters::<G>::from_fraction(1, 2, 5).unwrap().threshold,
+            3
+        );
+        assert_eq!(
+            Parameters::<G>::from_fraction(1, 1, 4).unwrap().threshold,
+            4
+        );
+
+        let expected =
+            Parameters::<G>::new(NonZeroUsize::new(6).unwrap(), NonZeroUsize::new(9).unwrap());
+        assert_eq!(parameters.to_bytes(), expected.to_bytes());
+
+        assert!(Parameters::<G>::from_fraction(2, 0, 9).is_err());
+        assert!(Parameters::<G>::from_fraction(0, 3, 9).is_err());
+        assert!(Parameters::<G>::from_fraction(1, 10, 9).is_err());
+        assert!(Parameters::<G>::from_fraction(4, 3, 9).is_err());
+        assert!(Parameters::<G>::from_fraction(usize::MAX, 1, 9).is_err());
+    }
 }
@@ -0,0 +1,154 @@
+use crate::{
+    deserialize_g, deserialize_g_vec, evaluate_public_polynomial, serialize_g, serialize_g_vec,
+    DkgHasher, DkgResult, Error, Parameters, SchnorrSignature, Sha256Hasher,
+};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+const MANIFEST_DOMAIN: &[u8] = b"gennaro-dkg ceremony manifest";
+
+/// A signed record of the outcome of a completed DKG.
+///
+/// See [`Participant::manifest`](crate::Participant::manifest).
+/// The manifest is signed by one participant with its secret share so it can
+/// be checked against the verification share derived from the commitments
+/// with [`CeremonyManifest::verify_manifest`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CeremonyManifest<G: Group + GroupEncoding + Default> {
+    /// The parameters used in the DKG
+    #[serde(bound(serialize = "Parameters<G>: Serialize"))]
+    #[serde(bound(deserialize = "Parameters<G>: Deserialize<'de>"))]
+    pub parameters: Parameters<G>,
+    /// The fingerprint of the parameters, see [`Parameters::fingerprint`]
+    pub parameters_fingerprint: [u8; 32],
+    /// The ids of the participants that hold a share of the key
+    pub participant_ids: BTreeSet<usize>,
+    /// The ids up to the limit that do not hold a share of the key
+    pub dropped_ids: BTreeSet<usize>,
+    /// The group public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The combined feldman commitments
+    #[serde(
+        serialize_with = "serialize_g_vec",
+        deserialize_with = "deserialize_g_vec"
+    )]
+    pub public_commitments: Vec<G>,
+    /// The hash of all the fields above.
+    ///
+    /// This covers the manifest's own fields and not the round broadcasts.
+    pub manifest_hash: [u8; 32],
+    /// The id of the participant that signed the manifest
+    pub signer: usize,
+    /// A signature over the manifest hash under the signer's secret share
+    #[serde(bound(serialize = "SchnorrSignature<G>: Serialize"))]
+    #[serde(bound(deserialize = "SchnorrSignature<G>: Deserialize<'de>"))]
+    pub signature: SchnorrSignature<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> CeremonyManifest<G> {
+    /// Assemble and sign the manifest as participant `signer`
+    /// using the hasher `H` for the fingerprint and manifest hash
+    pub(crate) fn new<H: DkgHasher>(
+        parameters: Parameters<G>,
+        participant_ids: BTreeSet<usize>,
+        public_commitments: Vec<G>,
+        signer: usize,
+        secret_share: &G::Scalar,
+        rng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let parameters_fingerprint = parameters.fingerprint_with::<H>();
+        let dropped_ids = (1..=parameters.limit)
+            .filter(|id| !participant_ids.contains(id))
+            .collect();
+        let mut manifest = Self {
+            parameters,
+            parameters_fingerprint,
+            participant_ids,
+            dropped_ids,
+            public_key: public_commitments[0],
+            public_commitments,
+            manifest_hash: [0u8; 32],
+            signer,
+            signature: SchnorrSignature {
+                commitment: G::identity(),
+                response: G::Scalar::default(),
+            },
+        };
+        manifest.manifest_hash = manifest.hash::<H>();
+        manifest.signature = SchnorrSignature::sign_with_generator(
+            parameters.message_generator,
+            secret_share,
+            &manifest.manifest_hash,
+            rng,
+        );
+        manifest
+    }
+
+    /// Recompute the fingerprint and manifest hash and check the fields
+    /// are consistent with each other and the signature is valid.
+    ///
+    /// Throws [`Error::InvalidInput`] naming the first inconsistency found
+    /// or [`Error::BadSignature`] if the signature is invalid.
+    pub fn verify_manifest(&self) -> DkgResult<()> {
+        self.verify_manifest_with::<Sha256Hasher>()
+    }
+
+    /// Verify a manifest created with the hasher `H`,
+    /// see [`CeremonyManifest::verify_manifest`]
+    pub fn verify_manifest_with<H: DkgHasher>(&self) -> DkgResult<()> {
+        let inconsistent = |reason: &str| Err(Error::InvalidInput(reason.to_string()));
+        if self.parameters_fingerprint != self.parameters.fingerprint_with::<H>() {
+            return inconsistent("the parameters fingerprint does not match the parameters");
+        }
+        if self.public_commitments.is_empty()
+            || self.public_commitments.len() != self.parameters.threshold
+        {
+            return inconsistent("the number of commitments does not match the threshold");
+        }
+        if self.public_commitments[0] != self.public_key {
+            return inconsistent("the public key does not match the commitments");
+        }
+        if self.participant_ids.len() < self.parameters.threshold {
+            return inconsistent("fewer participants than the threshold");
+        }
+        let all_ids = (1..=self.parameters.limit).collect::<BTreeSet<_>>();
+        if !self.participant_ids.is_subset(&all_ids)
+            || self.dropped_ids != &all_ids - &self.participant_ids
+        {
+            return inconsistent("the participant ids and dropped ids do not cover the limit");
+        }
+        if self.manifest_hash != self.hash::<H>() {
+            return inconsistent("the manifest hash does not match the manifest");
+        }
+        if !self.participant_ids.contains(&self.signer) {
+            return inconsistent("the signer is not a participant");
+        }
+        let verification_share = evaluate_public_polynomial(&self.public_commitments, self.signer);
+        if !self.signature.verify_with_generator(
+            self.parameters.message_generator,
+            verification_share,
+            &self.manifest_hash,
+        ) {
+            return Err(Error::BadSignature { from: self.signer });
+        }
+        Ok(())
+    }
+
+    fn hash<H: DkgHasher>(&self) -> [u8; 32] {
+        let mut bytes = self.parameters_fingerprint.to_vec();
+        for ids in [&self.participant_ids, &self.dropped_ids] {
+            bytes.extend_from_slice(&(ids.len() as u64).to_be_bytes());
+            for id in ids {
+                bytes.extend_from_slice(&(*id as u64).to_be_bytes());
+            }
+        }
+        bytes.extend_from_slice(self.public_key.to_bytes().as_ref());
+        for commitment in &self.public_commitments {
+            bytes.extend_from_slice(commitment.to_bytes().as_ref());
+        }
+        H::digest(&[MANIFEST_DOMAIN, bytes.as_slice()])
+    }
+}
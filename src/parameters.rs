@@ -1,5 +1,11 @@
 use super::*;
 
+/// The largest `limit` a participant accepts.
+///
+/// Share identifiers are a single byte so this is also the
+/// largest number of shares that can be created.
+pub const MAX_PARTICIPANTS: usize = u8::MAX as usize;
+
 /// The parameters used by the DKG participants.
 /// This must be the same for all of them otherwise the protocol
 /// will abort.
@@ -11,6 +17,14 @@ pub struct Parameters<G: Group + GroupEncoding + Default> {
     pub(crate) message_generator: G,
     #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
     pub(crate) blinder_generator: G,
+    #[serde(
+        default = "default_identity",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
+    pub(crate) coordinator: G,
+    #[serde(default)]
+    pub(crate) max_faults: Option<usize>,
 }
 
 impl<G: Group + GroupEncoding + Default> Default for Parameters<G> {
@@ -20,26 +34,79 @@ impl<G: Group + GroupEncoding + Default> Default for Parameters<G> {
             limit: 0,
             message_generator: G::identity(),
             blinder_generator: G::identity(),
+            coordinator: G::identity(),
+            max_faults: None,
         }
     }
 }
 
 impl<G: Group + GroupEncoding + Default> Parameters<G> {
     /// Create regular parameters with the message_generator as the default generator
-    /// and a random blinder_generator
+    /// and a random blinder_generator.
+    ///
+    /// The blinder_generator is derived deterministically from the
+    /// SHA-256 hash of the message_generator's encoding.
     pub fn new(threshold: NonZeroUsize, limit: NonZeroUsize) -> Self {
         let message_generator = G::generator();
-        let mut seed = [0u8; 32];
-        seed.copy_from_slice(&message_generator.to_bytes().as_ref()[0..32]);
+        // Hash the full encoding so every byte contributes regardless of its length
+        let seed = Sha256Hasher::digest(&[message_generator.to_bytes().as_ref()]);
         let rng = rand_chacha::ChaChaRng::from_seed(seed);
         Self {
             threshold: threshold.get(),
             limit: limit.get(),
             message_generator: G::generator(),
-            blinder_generator: G::random(rng),
+            blinder_generator: random_subgroup_point(rng),
+            coordinator: G::identity(),
+            max_faults: None,
         }
     }
 
+    /// Create parameters with the message_generator as the default generator
+    /// and a blinder_generator derived from `seed` using the hasher `H`.
+    ///
+    /// All participants that use the same seed and hasher get the same parameters.
+    pub fn with_seed<H: DkgHasher>(
+        threshold: NonZeroUsize,
+        limit: NonZeroUsize,
+        seed: &[u8],
+    ) -> Self {
+        let seed = H::digest(&[&b"gennaro-dkg blinder generator"[..], seed]);
+        let rng = rand_chacha::ChaChaRng::from_seed(seed);
+        Self {
+            threshold: threshold.get(),
+            limit: limit.get(),
+            message_generator: G::generator(),
+            blinder_generator: random_subgroup_point(rng),
+            coordinator: G::identity(),
+            max_faults: None,
+        }
+    }
+
+    /// Create parameters like [`Parameters::new`] with the threshold given as a
+    /// fraction of `limit`, rounded up so at least that fraction is needed.
+    ///
+    /// For example a two-thirds majority of 9 is `from_fraction(2, 3, 9)`
+    /// which has a threshold of 6.
+    ///
+    /// Throws an error if `denominator` is zero, the computation overflows, or
+    /// the threshold is not between 2 and `limit` as [`ParametersBuilder::build`] requires.
+    pub fn from_fraction(numerator: usize, denominator: usize, limit: usize) -> DkgResult<Self> {
+        if denominator == 0 {
+            return Err(Error::InvalidInput(
+                "the denominator cannot be zero".to_string(),
+            ));
+        }
+        let threshold = limit
+            .checked_mul(numerator)
+            .and_then(|n| n.checked_add(denominator - 1))
+            .map(|n| n / denominator)
+            .ok_or_else(|| Error::InvalidInput("the threshold overflows".to_string()))?;
+        ParametersBuilder::new()
+            .threshold(threshold)
+            .limit(limit)
+            .build()
+    }
+
     /// Use the provided parameters
     pub fn with_generators(
         threshold: NonZeroUsize,
@@ -52,6 +119,301 @@ impl<G: Group + GroupEncoding + Default> Parameters<G> {
             limit: limit.get(),
             message_generator,
             blinder_generator,
+            coordinator: G::identity(),
+            max_faults: None,
+        }
+    }
+
+    /// Enable coordinated mode with the coordinator's signature verification key.
+    ///
+    /// Participants then accept the valid set and public key signed by the
+    /// coordinator instead of the round 2 and round 4 echo broadcasts.
+    /// The coordinator is trusted for liveness but never sees any secrets.
+    pub fn with_coordinator(mut self, coordinator: G) -> Self {
+        self.coordinator = coordinator;
+        self
+    }
+
+    /// Abort in round 3 with [`AbortReason::TooManyFaults`] if more than
+    /// `max_faults` participants are dropped from the valid set.
+    ///
+    /// This is a local policy for deployments that treat many faults as
+    /// evidence of an attack. It is not part of [`Parameters::to_bytes`]
+    /// and does not need to match between participants.
+    pub fn with_max_faults(mut self, max_faults: usize) -> Self {
+        self.max_faults = Some(max_faults);
+        self
+    }
+
+    /// The most participants that can be dropped before aborting,
+    /// or [`None`] if any number is tolerated while the threshold is met
+    pub fn max_faults(&self) -> Option<usize> {
+        self.max_faults
+    }
+
+    /// The coordinator's signature verification key,
+    /// or [`None`] if coordinated mode is not enabled
+    pub fn coordinator(&self) -> Option<G> {
+        if self.coordinator.is_identity().into() {
+            None
+        } else {
+            Some(self.coordinator)
         }
     }
+
+    /// Serialize to a compact fixed layout: the threshold and limit as varints
+    /// followed by the raw encodings of the message and blinder generators.
+    ///
+    /// The coordinator key is appended only if coordinated mode is enabled.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Uint::from(self.threshold).to_vec();
+        bytes.extend_from_slice(&Uint::from(self.limit).to_vec());
+        bytes.extend_from_slice(self.message_generator.to_bytes().as_ref());
+        bytes.extend_from_slice(self.blinder_generator.to_bytes().as_ref());
+        if let Some(coordinator) = self.coordinator() {
+            bytes.extend_from_slice(coordinator.to_bytes().as_ref());
+        }
+        bytes
+    }
+
+    /// The SHA-256 hash of [`Parameters::to_bytes`] for comparing
+    /// parameters without exchanging them in full
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint_with::<Sha256Hasher>()
+    }
+
+    /// The hash of [`Parameters::to_bytes`] using the hasher `H`,
+    /// see [`Parameters::fingerprint`]
+    pub fn fingerprint_with<H: DkgHasher>(&self) -> [u8; 32] {
+        H::digest(&[
+            &b"gennaro-dkg parameters fingerprint"[..],
+            self.to_bytes().as_slice(),
+        ])
+    }
+
+    /// Deserialize from the layout of [`Parameters::to_bytes`].
+    ///
+    /// Malformed input returns an error and never panics.
+    /// The values are not validated, use [`ParametersBuilder`] for that.
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        let (threshold, bytes) = Self::read_varint(bytes)?;
+        let (limit, bytes) = Self::read_varint(bytes)?;
+        let (message_generator, bytes) = Self::read_point(bytes)?;
+        let (blinder_generator, bytes) = Self::read_point(bytes)?;
+        let coordinator = if bytes.is_empty() {
+            G::identity()
+        } else {
+            let (coordinator, bytes) = Self::read_point(bytes)?;
+            if !bytes.is_empty() {
+                return Err(Error::SerializationError(
+                    "trailing bytes after the parameters".to_string(),
+                ));
+            }
+            coordinator
+        };
+        Ok(Self {
+            threshold,
+            limit,
+            message_generator,
+            blinder_generator,
+            coordinator,
+            max_faults: None,
+        })
+    }
+
+    fn read_varint(bytes: &[u8]) -> DkgResult<(usize, &[u8])> {
+        let invalid = || Error::SerializationError("invalid varint".to_string());
+        let length = Uint::peek(bytes).ok_or_else(invalid)?;
+        let value = Uint::try_from(&bytes[..length]).map_err(|_| invalid())?.0;
+        let value = usize::try_from(value).map_err(|_| invalid())?;
+        Ok((value, &bytes[length..]))
+    }
+
+    fn read_point(bytes: &[u8]) -> DkgResult<(G, &[u8])> {
+        let mut repr = G::Repr::default();
+        let length = repr.as_ref().len();
+        if bytes.len() < length {
+            return Err(Error::SerializationError("truncated point".to_string()));
+        }
+        repr.as_mut().copy_from_slice(&bytes[..length]);
+        let point = decode_point::<G>(&repr)
+            .ok_or_else(|| Error::SerializationError("invalid point".to_string()))?;
+        Ok((point, &bytes[length..]))
+    }
+
+    /// Check that `other` matches these parameters.
+    ///
+    /// Returns [`Error::ParametersMismatch`] naming the first field that differs.
+    pub fn validate_against(&self, other: &Parameters<G>) -> DkgResult<()> {
+        let field = if self.threshold != other.threshold {
+            "threshold"
+        } else if self.limit != other.limit {
+            "limit"
+        } else if self.message_generator != other.message_generator {
+            "message_generator"
+        } else if self.blinder_generator != other.blinder_generator {
+            "blinder_generator"
+        } else if self.coordinator != other.coordinator {
+            "coordinator"
+        } else {
+            return Ok(());
+        };
+        Err(Error::ParametersMismatch { field })
+    }
+}
+
+/// Builds [`Parameters`] and checks all invariants once in [`ParametersBuilder::build`].
+///
+/// The message generator defaults to the curve's generator. The blinder
+/// generator is either set explicitly or derived from the session id
+/// using the hasher `H`, otherwise it is derived the same as [`Parameters::new`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ParametersBuilder<G: Group + GroupEncoding + Default, H: DkgHasher = Sha256Hasher> {
+    threshold: Option<usize>,
+    limit: Option<usize>,
+    message_generator: Option<G>,
+    blinder_generator: Option<G>,
+    session_id: Option<[u8; 32]>,
+    coordinator: Option<G>,
+    max_faults: Option<usize>,
+    hasher: PhantomData<H>,
+}
+
+impl<G: Group + GroupEncoding + Default> ParametersBuilder<G> {
+    /// Start building parameters that use [`Sha256Hasher`]
+    pub fn new() -> Self {
+        Self::with_hasher()
+    }
+}
+
+impl<G: Group + GroupEncoding + Default, H: DkgHasher> ParametersBuilder<G, H> {
+    /// Start building parameters that use the hasher `H` for the session id
+    pub fn with_hasher() -> Self {
+        Self {
+            threshold: None,
+            limit: None,
+            message_generator: None,
+            blinder_generator: None,
+            session_id: None,
+            coordinator: None,
+            max_faults: None,
+            hasher: PhantomData,
+        }
+    }
+
+    /// The number of participants needed to reconstruct the secret
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// The number of participants
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The generator used for the secret
+    pub fn message_generator(mut self, message_generator: G) -> Self {
+        self.message_generator = Some(message_generator);
+        self
+    }
+
+    /// The generator used for the blinder
+    pub fn blinder_generator(mut self, blinder_generator: G) -> Self {
+        self.blinder_generator = Some(blinder_generator);
+        self
+    }
+
+    /// Bind the parameters to a session.
+    ///
+    /// The blinder generator is derived from the session id so every
+    /// session uses an independent blinder generator.
+    pub fn session_id(mut self, session_id: &[u8]) -> Self {
+        self.session_id = Some(H::digest(&[session_id]));
+        self
+    }
+
+    /// Enable coordinated mode, see [`Parameters::with_coordinator`]
+    pub fn coordinator(mut self, coordinator: G) -> Self {
+        self.coordinator = Some(coordinator);
+        self
+    }
+
+    /// Abort after too many faults, see [`Parameters::with_max_faults`]
+    pub fn max_faults(mut self, max_faults: usize) -> Self {
+        self.max_faults = Some(max_faults);
+        self
+    }
+
+    /// Create the parameters.
+    ///
+    /// Throws an error if the threshold or limit is missing, the threshold is
+    /// less than 2 or greater than the limit, the limit exceeds [`MAX_PARTICIPANTS`],
+    /// a generator is the identity, the generators are equal, or both a
+    /// blinder generator and a session id were provided.
+    pub fn build(self) -> DkgResult<Parameters<G>> {
+        let threshold = self
+            .threshold
+            .ok_or_else(|| Error::InvalidInput("the threshold is required".to_string()))?;
+        let limit = self
+            .limit
+            .ok_or_else(|| Error::InvalidInput("the limit is required".to_string()))?;
+        if threshold < 2 || threshold > limit {
+            return Err(Error::InvalidInput(format!(
+                "the threshold {} must be between 2 and the limit {}",
+                threshold, limit
+            )));
+        }
+        if limit > MAX_PARTICIPANTS {
+            return Err(Error::InvalidInput(format!(
+                "the limit {} exceeds the maximum of {} participants",
+                limit, MAX_PARTICIPANTS
+            )));
+        }
+        let message_generator = self.message_generator.unwrap_or_else(G::generator);
+        let blinder_generator = match (self.blinder_generator, self.session_id) {
+            (Some(_), Some(_)) => {
+                return Err(Error::InvalidInput(
+                    "a blinder generator and a session id cannot both be used".to_string(),
+                ))
+            }
+            (Some(blinder_generator), None) => blinder_generator,
+            (None, Some(session_id)) => {
+                let seed = H::digest(&[
+                    &b"gennaro-dkg session blinder generator"[..],
+                    message_generator.to_bytes().as_ref(),
+                    &session_id,
+                ]);
+                random_subgroup_point(rand_chacha::ChaChaRng::from_seed(seed))
+            }
+            (None, None) => {
+                let seed = Sha256Hasher::digest(&[message_generator.to_bytes().as_ref()]);
+                random_subgroup_point(rand_chacha::ChaChaRng::from_seed(seed))
+            }
+        };
+        if (message_generator.is_identity() | blinder_generator.is_identity()).into() {
+            return Err(Error::InvalidInput(
+                "the generators cannot be the identity".to_string(),
+            ));
+        }
+        if message_generator == blinder_generator {
+            return Err(Error::InvalidInput(
+                "the generators must be different".to_string(),
+            ));
+        }
+        if !in_prime_order_subgroup(blinder_generator) {
+            return Err(Error::InvalidInput(
+                "the blinder generator is not in the prime-order subgroup".to_string(),
+            ));
+        }
+        Ok(Parameters {
+            threshold,
+            limit,
+            message_generator,
+            blinder_generator,
+            coordinator: self.coordinator.unwrap_or_else(G::identity),
+            max_faults: self.max_faults,
+        })
+    }
 }
@@ -1,29 +1,46 @@
+mod authenticated;
+mod coordinated;
+mod downgrade;
+mod enrollment;
+mod refresh;
 mod round1;
 mod round2;
 mod round3;
 mod round4;
 mod round5;
+mod round6;
+mod streaming;
+
+#[cfg(feature = "encrypted-shares")]
+mod ecies;
+#[cfg(feature = "encrypted-storage")]
+mod encrypted;
+#[cfg(test)]
+mod tests;
 
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::{self, Debug, Formatter};
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
-use std::sync::{Arc, Mutex};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
 
 use crate::*;
-use rand_core::{CryptoRng, RngCore};
+use rand_chacha::ChaChaRng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
-use soteria_rs::Protected;
 use vsss_rs::pedersen;
 use vsss_rs::{
-    elliptic_curve::{ff::Field, group::GroupEncoding, Group},
+    elliptic_curve::{ff::Field, group::GroupEncoding, Group, PrimeField},
     FeldmanVerifierSet, PedersenVerifierSet, Share,
 };
+use zeroize::Zeroizing;
 
 /// Secret Participant type
-pub type SecretParticipant<G> = Participant<SecretParticipantImpl<G>, G>;
+pub type SecretParticipant<G, S = ProtectedStore> = Participant<SecretParticipantImpl<G>, G, S>;
 
 /// Refresh Participant type
-pub type RefreshParticipant<G> = Participant<RefreshParticipantImpl<G>, G>;
+pub type RefreshParticipant<G, S = ProtectedStore> = Participant<RefreshParticipantImpl<G>, G, S>;
 
 /// Participant implementation
 pub trait ParticipantImpl<G: Group + GroupEncoding + Default> {
@@ -40,8 +57,16 @@ pub trait ParticipantImpl<G: Group + GroupEncoding + Default> {
 }
 
 /// A DKG participant FSM
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Participant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> {
+///
+/// Secrets held between rounds are kept in `S`, see [`SecretStore`].
+///
+/// The `Debug` output redacts the secret share and the pedersen components.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Participant<
+    I: ParticipantImpl<G>,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore = ProtectedStore,
+> {
     id: usize,
     #[serde(bound(serialize = "GennaroDkgPedersenResult<G>: Serialize"))]
     #[serde(bound(deserialize = "GennaroDkgPedersenResult<G>: Deserialize<'de>"))]
@@ -50,29 +75,111 @@ pub struct Participant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default
     limit: usize,
     round: Round,
     #[serde(with = "secret_share")]
-    secret_share: Arc<Mutex<Protected>>,
+    secret_share: S,
+    #[cfg(feature = "signed-echo")]
+    #[serde(with = "secret_share")]
+    echo_key: S,
     #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
     public_key: G,
+    #[serde(
+        serialize_with = "serialize_g_vec",
+        deserialize_with = "deserialize_g_vec"
+    )]
+    public_commitments: Vec<G>,
     #[serde(bound(serialize = "Round1BroadcastData<G>: Serialize"))]
     #[serde(bound(deserialize = "Round1BroadcastData<G>: Deserialize<'de>"))]
     round1_broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
     #[serde(with = "protected")]
-    round1_p2p_data: BTreeMap<usize, Arc<Mutex<Protected>>>,
+    round1_p2p_data: BTreeMap<usize, S>,
+    #[serde(default = "BTreeMap::new", with = "protected")]
+    preloaded_p2p_data: BTreeMap<usize, S>,
+    #[serde(default)]
+    round2_echo_data: BTreeMap<usize, Round2EchoBroadcastData>,
     valid_participant_ids: BTreeSet<usize>,
+    #[serde(default)]
+    contributors: BTreeSet<usize>,
+    #[serde(default)]
+    share_in_sink: bool,
+    #[serde(default)]
+    requires_approval: bool,
+    #[serde(default)]
+    valid_set_approved: bool,
+    #[serde(default)]
+    verifier_only: bool,
+    #[serde(
+        default = "default_identity",
+        serialize_with = "serialize_g",
+        deserialize_with = "deserialize_g"
+    )]
+    coordinator: G,
+    #[serde(default)]
+    max_faults: Option<usize>,
     participant_impl: I,
+    #[serde(skip)]
+    on_drop: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    #[serde(skip)]
+    subgroup_check: Option<fn(&G) -> bool>,
+    #[serde(skip, default = "Option::default")]
+    signing_key: Option<S>,
+    #[serde(skip)]
+    verifying_keys: BTreeMap<usize, G>,
+}
+
+/// A participant whose ceremony was cancelled with [`Participant::abort`].
+///
+/// It holds no secret material and cannot run any further rounds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AbortedParticipant {
+    id: usize,
+    round: Round,
+}
+
+impl AbortedParticipant {
+    /// The identifier of the aborted participant
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    /// The round the participant was in when it aborted
+    pub fn get_round(&self) -> Round {
+        self.round
+    }
 }
 
-impl<I, G> Participant<I, G>
+impl<I, G, S> Debug for Participant<I, G, S>
+where
+    I: ParticipantImpl<G>,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Participant")
+            .field("id", &self.id)
+            .field("round", &self.round)
+            .field("threshold", &self.threshold)
+            .field("limit", &self.limit)
+            .field("valid_participant_ids", &self.valid_participant_ids)
+            .field("public_key", &self.public_key)
+            .field("components", &"<redacted>")
+            .field("secret_share", &"<redacted>")
+            .finish()
+    }
+}
+
+impl<I, G, S> Participant<I, G, S>
 where
     I: ParticipantImpl<G> + Default,
     G: Group + GroupEncoding + Default,
+    S: SecretStore,
 {
     /// Create a new participant to generate a new key share
+    ///
+    /// Throws [`Error::RngFailure`] if the OS entropy source is unavailable.
     pub fn new(id: NonZeroUsize, parameters: Parameters<G>) -> DkgResult<Self> {
-        let rng = rand_core::OsRng;
-        let secret = I::secret(rng);
-        let blinder = G::Scalar::random(rng);
-        Self::initialize(id, parameters, secret, blinder)
+        let mut rng = entropy_rng()?;
+        let secret = I::secret(&mut rng);
+        let blinder = G::Scalar::random(&mut rng);
+        Self::initialize(id, parameters, secret, blinder, rng)
     }
 
     /// Create a new participant with an existing secret.
@@ -85,10 +192,104 @@ where
         shares_ids: &[G::Scalar],
         index: usize,
     ) -> DkgResult<Self> {
-        let mut rng = rand_core::OsRng;
+        let mut rng = entropy_rng()?;
         let blinder = G::Scalar::random(&mut rng);
         let secret = Self::lagrange_interpolation(share, shares_ids, index)?;
-        Self::initialize(id, parameters, secret, blinder)
+        Self::initialize(id, parameters, secret, blinder, rng)
+    }
+
+    /// Create a fresh participant to restart the protocol with only
+    /// the participants in `valid_ids`, for example after a participant was dropped.
+    ///
+    /// The new participant uses new randomness, the same threshold and generators,
+    /// and a limit equal to the size of `valid_ids`. Since share identifiers must be
+    /// in `1..=limit`, participants are renumbered by their position in `valid_ids`
+    /// i.e. the smallest id becomes 1, the next 2, and so on.
+    ///
+    /// Throws an error if this participant is not in `valid_ids` or
+    /// `valid_ids` is smaller than the threshold.
+    pub fn restart_with(&self, valid_ids: BTreeSet<usize>) -> DkgResult<Self> {
+        let position = valid_ids
+            .iter()
+            .position(|id| *id == self.id)
+            .ok_or_else(|| {
+                Error::InitializationError(format!(
+                    "secret_participant {} is not in the valid set",
+                    self.id
+                ))
+            })?;
+        if valid_ids.len() < self.threshold {
+            return Err(Error::InitializationError(
+                "Not enough valid participants, below the threshold".to_string(),
+            ));
+        }
+        let mut parameters = self.get_parameters();
+        parameters.limit = valid_ids.len();
+        let id = NonZeroUsize::new(position + 1).expect("position + 1 is never zero");
+        Self::new(id, parameters)
+    }
+
+    /// Create a participant that checks the data of the other participants
+    /// but never contributes a polynomial or holds a secret share,
+    /// for example a neutral auditor.
+    ///
+    /// The participant starts in round 2 and is called with the round 1
+    /// broadcasts and no peer-to-peer data. Rounds 2 to 4 check the
+    /// commitments, the valid sets and the public key like any other
+    /// participant, but the shares cannot be checked. After round 4 it holds
+    /// the group public key and commitments and
+    /// [`Participant::try_get_secret_share`] returns [`Error::VerifierOnly`].
+    ///
+    /// `id` must be larger than the limit so no dealer creates a share for it.
+    /// The other participants never include it in their valid sets, so its
+    /// round 2 echo and later messages do not need to be sent to them.
+    /// Coordinated mode is not supported.
+    ///
+    /// Throws an error if `id` is not larger than the limit or the parameters are invalid.
+    pub fn new_verifier_only(id: NonZeroUsize, parameters: Parameters<G>) -> DkgResult<Self> {
+        Self::check_limit(&parameters)?;
+        if id.get() <= parameters.limit {
+            return Err(Error::InitializationError(format!(
+                "The verifier id {} must be larger than the limit {}",
+                id, parameters.limit
+            )));
+        }
+        if parameters.coordinator().is_some() {
+            return Err(Error::InitializationError(
+                "Verifier-only participants do not support coordinated mode".to_string(),
+            ));
+        }
+        if (parameters.message_generator.is_identity() | parameters.blinder_generator.is_identity())
+            .into()
+            || !in_prime_order_subgroup(parameters.blinder_generator)
+        {
+            return Err(Error::InitializationError("Invalid generators".to_string()));
+        }
+        // No polynomial is contributed so the commitments are all the identity
+        let commitments = vec![G::identity(); parameters.threshold];
+        let components = GennaroDkgPedersenResult {
+            blinder: G::Scalar::ZERO,
+            secret_shares: Vec::new(),
+            blinder_shares: Vec::new(),
+            feldman_verifier_set: Vec::<G>::feldman_set_with_generator_and_verifiers(
+                parameters.message_generator,
+                &commitments,
+            ),
+            pedersen_verifier_set: Vec::<G>::pedersen_set_with_generators_and_verifiers(
+                parameters.message_generator,
+                parameters.blinder_generator,
+                &commitments,
+            ),
+        };
+        let mut participant = Self::assemble(id, parameters, components);
+        participant.verifier_only = true;
+        participant.round = Round::Two;
+        Ok(participant)
+    }
+
+    /// Whether this participant was created with [`Participant::new_verifier_only`]
+    pub fn is_verifier_only(&self) -> bool {
+        self.verifier_only
     }
 
     fn initialize(
@@ -96,8 +297,9 @@ where
         parameters: Parameters<G>,
         secret: G::Scalar,
         blinder: G::Scalar,
+        rng: impl RngCore + CryptoRng,
     ) -> DkgResult<Self> {
-        let rng = rand_core::OsRng;
+        Self::check_limit(&parameters)?;
         let components = pedersen::split_secret(
             parameters.threshold,
             parameters.limit,
@@ -107,8 +309,104 @@ where
             Some(parameters.blinder_generator),
             rng,
         )?;
-        let components = GennaroDkgPedersenResult::from(components);
+        Self::from_components(id, parameters, GennaroDkgPedersenResult::from(components))
+    }
+
+    /// Create a new participant that uses the fixed polynomials
+    /// `secret_coefficients` and `blinder_coefficients` instead of random ones.
+    ///
+    /// The constant terms are the secret and blinder. Both must contain
+    /// exactly `threshold` coefficients.
+    ///
+    /// This is only for known-answer tests, the polynomials must be random
+    /// for the protocol to be secure.
+    #[cfg(any(test, feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+    pub fn from_polynomials(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        secret_coefficients: Vec<G::Scalar>,
+        blinder_coefficients: Vec<G::Scalar>,
+    ) -> DkgResult<Self> {
+        if secret_coefficients.len() != parameters.threshold
+            || blinder_coefficients.len() != parameters.threshold
+        {
+            return Err(Error::InitializationError(
+                "The number of coefficients must equal the threshold".to_string(),
+            ));
+        }
+        Self::check_limit(&parameters)?;
+        let evaluate = |coefficients: &[G::Scalar], x: usize| {
+            let x = G::Scalar::from(x as u64);
+            coefficients
+                .iter()
+                .rev()
+                .fold(G::Scalar::ZERO, |acc, c| acc * x + c)
+        };
+        let mut secret_shares = Vec::with_capacity(parameters.limit);
+        let mut blinder_shares = Vec::with_capacity(parameters.limit);
+        for x in 1..=parameters.limit {
+            secret_shares.push(encode_share(x, evaluate(&secret_coefficients, x))?);
+            blinder_shares.push(encode_share(x, evaluate(&blinder_coefficients, x))?);
+        }
+        let feldman_commitments = secret_coefficients
+            .iter()
+            .map(|a| parameters.message_generator * a)
+            .collect::<Vec<G>>();
+        let pedersen_commitments = secret_coefficients
+            .iter()
+            .zip(blinder_coefficients.iter())
+            .map(|(a, b)| parameters.message_generator * a + parameters.blinder_generator * b)
+            .collect::<Vec<G>>();
+        let components = GennaroDkgPedersenResult {
+            blinder: blinder_coefficients[0],
+            secret_shares,
+            blinder_shares,
+            feldman_verifier_set: Vec::<G>::feldman_set_with_generator_and_verifiers(
+                parameters.message_generator,
+                &feldman_commitments,
+            ),
+            pedersen_verifier_set: Vec::<G>::pedersen_set_with_generators_and_verifiers(
+                parameters.message_generator,
+                parameters.blinder_generator,
+                &pedersen_commitments,
+            ),
+        };
+        Self::from_components(id, parameters, components)
+    }
+
+    /// Reject degenerate thresholds and limits that would exhaust resources
+    /// before any allocation happens
+    fn check_limit(parameters: &Parameters<G>) -> DkgResult<()> {
+        // A single share is the secret itself and there are no peers to
+        // verify it, including the 1-of-1 case which is plain key generation
+        if parameters.threshold < 2 {
+            return Err(Error::InitializationError(format!(
+                "The threshold {} must be at least 2, use plain key generation for a single participant",
+                parameters.threshold
+            )));
+        }
+        if parameters.limit > MAX_PARTICIPANTS {
+            return Err(Error::InitializationError(format!(
+                "The limit {} exceeds the maximum of {} participants",
+                parameters.limit, MAX_PARTICIPANTS
+            )));
+        }
+        Ok(())
+    }
 
+    fn from_components(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        components: GennaroDkgPedersenResult<G>,
+    ) -> DkgResult<Self> {
+        // There is no share for ids beyond the limit
+        if id.get() > parameters.limit {
+            return Err(Error::InitializationError(format!(
+                "id {} exceeds limit {}",
+                id, parameters.limit
+            )));
+        }
         if (components
             .pedersen_verifier_set
             .secret_generator()
@@ -121,6 +419,13 @@ where
         {
             return Err(Error::InitializationError("Invalid generators".to_string()));
         }
+        // A blinder generator with a small-order component breaks the binding
+        // of the pedersen commitments on curves with a cofactor
+        if !in_prime_order_subgroup(components.pedersen_verifier_set.blinder_generator()) {
+            return Err(Error::InitializationError(
+                "The blinder generator is not in the prime-order subgroup".to_string(),
+            ));
+        }
         let pedersen_commitments = components.pedersen_verifier_set.blind_verifiers();
         let feldman_commitments = components.feldman_verifier_set.verifiers();
         if pedersen_commitments.iter().any(|c| c.is_identity().into())
@@ -139,7 +444,15 @@ where
         {
             return Err(Error::InitializationError("Invalid shares".to_string()));
         }
-        Ok(Self {
+        Ok(Self::assemble(id, parameters, components))
+    }
+
+    fn assemble(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        components: GennaroDkgPedersenResult<G>,
+    ) -> Self {
+        Self {
             id: id.get(),
             components,
             threshold: parameters.threshold,
@@ -147,11 +460,27 @@ where
             round: Round::One,
             round1_broadcast_data: BTreeMap::new(),
             round1_p2p_data: BTreeMap::new(),
-            secret_share: Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO))),
+            preloaded_p2p_data: BTreeMap::new(),
+            round2_echo_data: BTreeMap::new(),
+            secret_share: protect_scalar(&G::Scalar::ZERO),
+            #[cfg(feature = "signed-echo")]
+            echo_key: protect_scalar(&G::Scalar::ZERO),
             public_key: G::identity(),
+            public_commitments: Vec::new(),
             valid_participant_ids: BTreeSet::new(),
+            coordinator: parameters.coordinator,
+            max_faults: parameters.max_faults,
             participant_impl: Default::default(),
-        })
+            on_drop: None,
+            subgroup_check: None,
+            signing_key: None,
+            verifying_keys: BTreeMap::new(),
+            contributors: BTreeSet::new(),
+            share_in_sink: false,
+            requires_approval: false,
+            valid_set_approved: false,
+            verifier_only: false,
+        }
     }
 
     /// The identifier associated with this secret_participant
@@ -159,6 +488,80 @@ where
         self.id
     }
 
+    /// A human readable description of the round this participant runs next
+    pub fn round_name(&self) -> &'static str {
+        self.round.name()
+    }
+
+    /// The number of rounds this participant still has to run,
+    /// including the current one.
+    ///
+    /// This is 1 once the key is computed since round 5 only confirms it.
+    pub fn rounds_remaining(&self) -> usize {
+        6 - usize::from(self.round)
+    }
+
+    /// The binary encoded size of the messages this participant sends
+    /// in its current round.
+    ///
+    /// The round 2 echo is sized as if every participant is valid, the upper bound
+    /// of its encoding, and without the optional `signed-echo` signature.
+    /// Nothing is sent in round 5 so both sizes are zero.
+    pub fn message_sizes(&self) -> DkgResult<RoundSizes> {
+        let (broadcast, p2p) = match self.round {
+            Round::One => {
+                let bdata = Round1BroadcastData {
+                    blinder_generator: self.components.pedersen_verifier_set.blinder_generator(),
+                    message_generator: self.components.pedersen_verifier_set.secret_generator(),
+                    pedersen_commitments: self
+                        .components
+                        .pedersen_verifier_set
+                        .blind_verifiers()
+                        .to_vec(),
+                    #[cfg(feature = "signed-echo")]
+                    echo_key: G::generator(),
+                };
+                let mut p2p = 0;
+                for (s, b) in self
+                    .components
+                    .secret_shares
+                    .iter()
+                    .zip(self.components.blinder_shares.iter())
+                    .filter(|(s, _)| s.identifier() as usize != self.id)
+                {
+                    p2p += Round1P2PData {
+                        secret_share: s.clone(),
+                        blind_share: b.clone(),
+                    }
+                    .to_bytes()?
+                    .len();
+                }
+                (bdata.to_bytes()?.len(), p2p)
+            }
+            Round::Two => (
+                Round2EchoBroadcastData::new((1..=self.limit).collect())
+                    .to_bytes()?
+                    .len(),
+                0,
+            ),
+            Round::Three => (self.round3_broadcast_data()?.to_bytes()?.len(), 0),
+            Round::Four => (
+                Round4EchoBroadcastData {
+                    public_key: self.public_key,
+                }
+                .to_bytes()?
+                .len(),
+                0,
+            ),
+            Round::Five => (0, 0),
+        };
+        Ok(RoundSizes {
+            round: self.round,
+            broadcast,
+            p2p,
+        })
+    }
+
     /// Returns true if this secret_participant is complete
     pub fn completed(&self) -> bool {
         self.round == Round::Five
@@ -179,19 +582,50 @@ where
         self.limit
     }
 
+    /// Return the parameters this participant was created with
+    pub fn get_parameters(&self) -> Parameters<G> {
+        Parameters {
+            threshold: self.threshold,
+            limit: self.limit,
+            message_generator: self.components.pedersen_verifier_set.secret_generator(),
+            blinder_generator: self.components.pedersen_verifier_set.blinder_generator(),
+            coordinator: self.coordinator,
+            max_faults: self.max_faults,
+        }
+    }
+
     /// Computed secret share.
     /// This value is useless until all rounds have been run
-    /// so [`None`] is returned until completion
+    /// so [`None`] is returned until completion or if it
+    /// was handed to a [`ShareSink`]
     pub fn get_secret_share(&self) -> Option<G::Scalar> {
-        if self.round == Round::Five {
-            let mut protected = self.secret_share.lock().ok()?;
-            let u = protected.unprotect()?;
-            u.field_element::<G::Scalar>().ok()
+        if self.round == Round::Five && !self.share_in_sink && !self.verifier_only {
+            unprotect_scalar(&self.secret_share)
         } else {
             None
         }
     }
 
+    /// Computed secret share.
+    ///
+    /// Throws [`Error::NotComplete`] until all rounds have been run
+    /// or [`Error::ShareInSink`] if it was handed to a [`ShareSink`].
+    /// A verifier-only participant throws [`Error::VerifierOnly`].
+    pub fn try_get_secret_share(&self) -> DkgResult<G::Scalar> {
+        if self.round != Round::Five {
+            return Err(Error::NotComplete);
+        }
+        if self.verifier_only {
+            return Err(Error::VerifierOnly);
+        }
+        if self.share_in_sink {
+            return Err(Error::ShareInSink);
+        }
+        unprotect_scalar(&self.secret_share).ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), "invalid secret unprotected".to_string())
+        })
+    }
+
     /// Computed public key
     /// This value is useless until all rounds have been run
     /// so [`None`] is returned until completion
@@ -203,11 +637,612 @@ where
         }
     }
 
+    /// Computed public key.
+    ///
+    /// Throws [`Error::NotComplete`] until all rounds have been run.
+    pub fn try_get_public_key(&self) -> DkgResult<G> {
+        self.get_public_key().ok_or(Error::NotComplete)
+    }
+
+    /// The computed public key in the curve's canonical `GroupEncoding`.
+    ///
+    /// For `WrappedEdwards` this is the 32 byte compressed Edwards point,
+    /// the same encoding as an Ed25519 public key.
+    /// [`None`] is returned until completion.
+    pub fn get_public_key_bytes(&self) -> Option<Vec<u8>> {
+        self.get_public_key()
+            .map(|pk| pk.to_bytes().as_ref().to_vec())
+    }
+
+    /// A short fingerprint of the computed public key for comparing out-of-band.
+    ///
+    /// This is the first 16 bytes of the SHA-256 hash of the public key encoding,
+    /// so participants can read them aloud to detect a split-brain outcome.
+    /// [`None`] is returned until completion.
+    pub fn key_fingerprint(&self) -> Option<[u8; 16]> {
+        self.key_fingerprint_with::<Sha256Hasher>()
+    }
+
+    /// The fingerprint of the computed public key using the hasher `H`,
+    /// see [`Participant::key_fingerprint`]
+    pub fn key_fingerprint_with<H: DkgHasher>(&self) -> Option<[u8; 16]> {
+        self.get_public_key().map(|pk| {
+            let digest = H::digest(&[&b"gennaro-dkg key fingerprint"[..], pk.to_bytes().as_ref()]);
+            let mut fingerprint = [0u8; 16];
+            fingerprint.copy_from_slice(&digest[..16]);
+            fingerprint
+        })
+    }
+
+    /// The computed secret share in the scalar's `PrimeField::to_repr` encoding.
+    ///
+    /// For `WrappedEdwards` this is the little-endian scalar and not an Ed25519
+    /// seed, so it must be used directly as the signing scalar without hashing or clamping.
+    /// [`None`] is returned until completion.
+    pub fn get_secret_share_bytes(&self) -> Option<Zeroizing<Vec<u8>>> {
+        self.get_secret_share()
+            .map(|share| Zeroizing::new(share.to_repr().as_ref().to_vec()))
+    }
+
+    /// This participant's additive contribution to the public key,
+    /// the commitment to the constant term of its secret polynomial.
+    ///
+    /// The contributions of all valid participants sum to the public key.
+    pub fn own_public_contribution(&self) -> G {
+        self.components.feldman_verifier_set.verifiers()[0]
+    }
+
+    /// The sum of all valid participants' feldman commitments.
+    ///
+    /// These commit to the polynomial whose evaluations are the secret shares.
+    /// Empty until round 4 completes.
+    pub fn get_public_commitments(&self) -> &[G] {
+        &self.public_commitments
+    }
+
+    /// This participant's commitments to its blinder polynomial `h^{b_j}`.
+    ///
+    /// Together with the pedersen and feldman commitments they allow an
+    /// external verifier to re-check the pedersen commitment equation.
+    /// They reveal the feldman commitments so they are only available
+    /// once round 3 has broadcast those.
+    ///
+    /// Throws an error if this participant has not completed round 3.
+    pub fn blinder_commitments(&self) -> DkgResult<Vec<G>> {
+        if self.round < Round::Four {
+            return Err(Error::RoundError(
+                Round::Three.into(),
+                "Blinder commitments are only available after round 3".to_string(),
+            ));
+        }
+        Ok(self
+            .components
+            .pedersen_verifier_set
+            .blind_verifiers()
+            .iter()
+            .zip(self.components.feldman_verifier_set.verifiers())
+            .map(|(p, c)| *p - c)
+            .collect())
+    }
+
+    /// The sum of the public key contributions accumulated so far.
+    ///
+    /// This is the identity until round 4 starts summing the
+    /// round 3 broadcast data and equals the public key once it finishes.
+    /// Useful for reporting progress in large committees.
+    pub fn public_key_so_far(&self) -> G {
+        self.public_key
+    }
+
     /// Return the list of valid participant ids
     pub fn get_valid_participant_ids(&self) -> &BTreeSet<usize> {
         &self.valid_participant_ids
     }
 
+    /// The peers that have not contributed data this participant expected.
+    ///
+    /// This is the valid participant set, or every id up to the limit before
+    /// round 2 completes, minus the ids present in the input of the most recent
+    /// round this participant ran and minus this participant. Before round 2
+    /// the peer-to-peer data stored with [`Participant::preload_p2p`] counts as
+    /// contributed. Round 5 does not change this participant so it is not tracked.
+    ///
+    /// Combined with an external timer this supports dropping slow peers
+    /// without this crate owning any timing.
+    pub fn missing_contributors(&self) -> BTreeSet<usize> {
+        let expected = if self.valid_participant_ids.is_empty() {
+            (1..=self.limit).collect()
+        } else {
+            self.valid_participant_ids.clone()
+        };
+        expected
+            .difference(&self.contributors)
+            .filter(|id| **id != self.id)
+            .copied()
+            .collect()
+    }
+
+    /// The peers whose data this participant successfully processed in `round`.
+    ///
+    /// Round 2 processes the round 1 data so this is every peer whose broadcast
+    /// and peer-to-peer data was stored. Rounds 3 and 4 keep the peers in the
+    /// valid set, which round 4 may have reduced. Nothing is received in round 1
+    /// and round 5 does not change this participant so both are always empty,
+    /// as is any round this participant has not completed yet.
+    pub fn processed_peers(&self, round: Round) -> BTreeSet<usize> {
+        if self.round <= round {
+            return BTreeSet::new();
+        }
+        match round {
+            Round::One | Round::Five => BTreeSet::new(),
+            Round::Two => self
+                .round1_broadcast_data
+                .keys()
+                .filter(|id| self.round1_p2p_data.contains_key(id))
+                .copied()
+                .collect(),
+            Round::Three | Round::Four => self
+                .valid_participant_ids
+                .iter()
+                .filter(|id| **id != self.id)
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// The peers whose round 1 data arrived over only one channel, as
+    /// [`Error::MissingP2PData`] or [`Error::MissingBroadcastData`].
+    ///
+    /// Round 2 excludes these peers from the valid set instead of failing,
+    /// so this report can be used to file complaints against them.
+    /// It is empty until round 2 completes.
+    pub fn missing_round1_data(&self) -> Vec<Error> {
+        let missing_p2p = self
+            .round1_broadcast_data
+            .keys()
+            .filter(|id| !self.round1_p2p_data.contains_key(id))
+            .map(|from| Error::MissingP2PData { from: *from });
+        let missing_broadcast = self
+            .round1_p2p_data
+            .keys()
+            .filter(|id| !self.round1_broadcast_data.contains_key(id))
+            .map(|from| Error::MissingBroadcastData { from: *from });
+        missing_p2p.chain(missing_broadcast).collect()
+    }
+
+    /// Iterate over the valid participant ids in ascending order
+    pub fn valid_participant_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.valid_participant_ids.iter().copied()
+    }
+
+    /// The number of valid participants
+    pub fn valid_participant_count(&self) -> usize {
+        self.valid_participant_ids.len()
+    }
+
+    /// A snapshot of the protocol state that is safe to attach to bug reports.
+    ///
+    /// Unlike serializing the participant this contains no secret material,
+    /// only counts of the received data and the valid and dropped ids.
+    /// Serialize it with a format like JSON to share it.
+    pub fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            id: self.id,
+            round: self.round,
+            threshold: self.threshold,
+            limit: self.limit,
+            parameters_fingerprint: data_encoding::HEXLOWER
+                .encode(&self.get_parameters().fingerprint()),
+            broadcasts_received: self.round1_broadcast_data.len(),
+            p2p_received: self.round1_p2p_data.len(),
+            p2p_preloaded: self.preloaded_p2p_data.len(),
+            echoes_received: self.round2_echo_data.len(),
+            valid_participant_ids: self.valid_participant_ids.clone(),
+            dropped_ids: self
+                .round1_broadcast_data
+                .keys()
+                .filter(|id| !self.valid_participant_ids.contains(id))
+                .copied()
+                .collect(),
+            verifier_only: self.verifier_only,
+        }
+    }
+
+    /// Whether participant `id` is in the valid set
+    pub fn is_valid_participant(&self, id: usize) -> bool {
+        self.valid_participant_ids.contains(&id)
+    }
+
+    /// Restrict the valid participants to those that are also in `ids`.
+    ///
+    /// This supports deployments that agree on the valid set out-of-band.
+    /// Participants that this participant did not verify in round 2 are never added.
+    ///
+    /// Throws an error if this participant is not in round 3, is not in `ids`,
+    /// or if the resulting set is below the threshold.
+    pub fn set_valid_participants(&mut self, ids: BTreeSet<usize>) -> DkgResult<()> {
+        self.check_round(Round::Three)?;
+        self.retain_valid_participants(&ids, Round::Three)
+    }
+
+    /// Call `cb` with the id of each participant this participant drops
+    /// because its data failed verification in round 2 or round 4.
+    ///
+    /// The ids are reported before the round checks whether enough
+    /// participants remain, so drops are reported even if the round fails.
+    /// A panic in `cb` is caught and does not affect this participant.
+    /// The observer is not serialized.
+    pub fn set_on_drop(&mut self, cb: impl Fn(usize) + Send + Sync + 'static) {
+        self.on_drop = Some(Arc::new(cb));
+    }
+
+    /// Check in round 4 that the computed public key is in the prime-order subgroup.
+    ///
+    /// This works for every curve, for prime-order curves the check always passes.
+    /// The setting is not serialized.
+    pub fn enable_subgroup_check(&mut self) {
+        self.subgroup_check = Some(|p: &G| in_prime_order_subgroup(*p));
+    }
+
+    /// Pause after round 2 until the valid participant set is approved.
+    ///
+    /// Round 3 returns [`Error::AwaitingApproval`] until
+    /// [`Participant::approve_valid_set`] is called so a human or policy engine
+    /// can inspect [`Participant::get_valid_participant_ids`] first.
+    /// The setting is serialized and kept by [`Participant::reset`].
+    pub fn require_valid_set_approval(&mut self) {
+        self.requires_approval = true;
+    }
+
+    /// Approve the valid participant set computed in round 2 so round 3 can run.
+    ///
+    /// Throws an error if this participant is not in round 3.
+    pub fn approve_valid_set(&mut self) -> DkgResult<()> {
+        self.check_round(Round::Three)?;
+        self.valid_set_approved = true;
+        Ok(())
+    }
+
+    /// Check the valid set was approved if approval is required
+    fn check_approval(&self) -> DkgResult<()> {
+        if self.requires_approval && !self.valid_set_approved {
+            return Err(Error::AwaitingApproval);
+        }
+        Ok(())
+    }
+
+    /// Report `ids` to the drop observer if one is set
+    fn notify_dropped(&self, ids: impl IntoIterator<Item = usize>) {
+        if let Some(cb) = &self.on_drop {
+            for id in ids {
+                let _ = catch_unwind(AssertUnwindSafe(|| cb(id)));
+            }
+        }
+    }
+
+    /// Cancel the ceremony and immediately erase all secret state:
+    /// the secret share, the secret and blinder shares and the blinder,
+    /// and all received peer-to-peer shares.
+    ///
+    /// Secrets held in a [`ProtectedStore`] are erased for every clone of this
+    /// participant, other stores only erase this participant's copy.
+    pub fn abort(mut self) -> AbortedParticipant {
+        self.wipe_secrets();
+        if let Some(signing_key) = self.signing_key.as_mut() {
+            signing_key.wipe();
+        }
+        AbortedParticipant {
+            id: self.id,
+            round: self.round,
+        }
+    }
+
+    /// Start a new independent DKG session with the same id and parameters.
+    ///
+    /// New random polynomials are generated, the prior secrets are erased
+    /// like [`Participant::abort`] and all round data and valid ids are cleared.
+    /// The drop observer, subgroup check and authentication keys are kept.
+    pub fn reset(&mut self) -> DkgResult<()> {
+        let id = NonZeroUsize::new(self.id)
+            .ok_or_else(|| Error::InitializationError("The participant id is zero".to_string()))?;
+        let mut fresh = if self.verifier_only {
+            Self::new_verifier_only(id, self.get_parameters())?
+        } else {
+            Self::new(id, self.get_parameters())?
+        };
+        self.wipe_secrets();
+        fresh.on_drop = self.on_drop.take();
+        fresh.subgroup_check = self.subgroup_check;
+        fresh.requires_approval = self.requires_approval;
+        fresh.signing_key = self.signing_key.take();
+        fresh.verifying_keys = std::mem::take(&mut self.verifying_keys);
+        *self = fresh;
+        Ok(())
+    }
+
+    fn wipe_secrets(&mut self) {
+        self.secret_share.wipe();
+        #[cfg(feature = "signed-echo")]
+        self.echo_key.wipe();
+        for share in self
+            .components
+            .secret_shares
+            .iter_mut()
+            .chain(self.components.blinder_shares.iter_mut())
+        {
+            share.zeroize();
+        }
+        self.components.secret_shares = Vec::new();
+        self.components.blinder_shares = Vec::new();
+        self.components.blinder = G::Scalar::ZERO;
+        for store in self
+            .round1_p2p_data
+            .values_mut()
+            .chain(self.preloaded_p2p_data.values_mut())
+        {
+            store.wipe();
+        }
+        self.round1_p2p_data.clear();
+        self.preloaded_p2p_data.clear();
+    }
+
+    /// Check that this participant is in the `expected` round
+    fn check_round(&self, expected: Round) -> DkgResult<()> {
+        if self.round != expected {
+            return Err(Error::RoundOrder {
+                expected: expected.into(),
+                got: self.round.into(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that enough valid participants remain to reconstruct the secret.
+    ///
+    /// When every participant is required, i.e. `threshold == limit`,
+    /// losing any participant aborts the protocol.
+    fn check_valid_participants(&self, valid_participant_ids: &BTreeSet<usize>) -> DkgResult<()> {
+        if valid_participant_ids.len() < self.threshold {
+            if self.threshold == self.limit {
+                return Err(Error::Abort(AbortReason::TooFewValidParticipants));
+            }
+            return Err(Error::InsufficientParticipants {
+                have: valid_participant_ids.len(),
+                need: self.threshold,
+            });
+        }
+        Ok(())
+    }
+
+    /// Abort if more participants than [`Parameters::max_faults`]
+    /// are outside `agreed_ids`
+    fn check_max_faults(&self, agreed_ids: &BTreeSet<usize>) -> DkgResult<()> {
+        if let Some(max_faults) = self.max_faults {
+            if self.limit - agreed_ids.len() > max_faults {
+                return Err(Error::Abort(AbortReason::TooManyFaults));
+            }
+        }
+        Ok(())
+    }
+
+    /// The round 3 broadcast with this participant's commitments and the
+    /// verification share of its current secret share
+    fn round3_broadcast_data(&self) -> DkgResult<Round3BroadcastData<G>> {
+        // A verifier-only participant holds no share
+        let verification_share = if self.verifier_only {
+            G::identity()
+        } else {
+            let secret_share =
+                unprotect_scalar::<S, G::Scalar>(&self.secret_share).ok_or_else(|| {
+                    Error::RoundError(
+                        Round::Three.into(),
+                        "invalid secret unprotected".to_string(),
+                    )
+                })?;
+            self.components.feldman_verifier_set.generator() * secret_share
+        };
+        Ok(Round3BroadcastData {
+            commitments: self.components.feldman_verifier_set.verifiers().to_vec(),
+            pedersen_commitments: self
+                .components
+                .pedersen_verifier_set
+                .blind_verifiers()
+                .to_vec(),
+            verification_share,
+        })
+    }
+
+    /// Remove all valid participants not in `ids` and their
+    /// contributions to the secret share
+    fn retain_valid_participants(&mut self, ids: &BTreeSet<usize>, round: Round) -> DkgResult<()> {
+        if !ids.contains(&self.id) {
+            return Err(Error::RoundError(
+                round.into(),
+                format!("secret_participant {} is not in the valid set", self.id),
+            ));
+        }
+        let valid_participant_ids = self
+            .valid_participant_ids
+            .intersection(ids)
+            .copied()
+            .collect::<BTreeSet<usize>>();
+        if valid_participant_ids.len() < self.threshold {
+            if self.threshold == self.limit {
+                return Err(Error::Abort(AbortReason::TooFewValidParticipants));
+            }
+            return Err(Error::RoundError(
+                round.into(),
+                "Not enough valid participants, below the threshold".to_string(),
+            ));
+        }
+
+        let mut secret_share =
+            unprotect_scalar::<S, G::Scalar>(&self.secret_share).ok_or_else(|| {
+                Error::RoundError(round.into(), "invalid secret unprotected".to_string())
+            })?;
+        for id in self
+            .valid_participant_ids
+            .difference(&valid_participant_ids)
+        {
+            let p2p = self
+                .round1_p2p_data
+                .get(id)
+                .and_then(unprotect_p2p)
+                .ok_or_else(|| {
+                    Error::RoundError(round.into(), "invalid secret unprotected".to_string())
+                })?;
+            secret_share -= p2p.secret_share.as_field_element::<G::Scalar>()?;
+        }
+        self.secret_share = protect_scalar(&secret_share);
+        self.valid_participant_ids = valid_participant_ids;
+        Ok(())
+    }
+
+    /// Export the key material needed by a threshold signer.
+    ///
+    /// Throws an error if the protocol has not completed.
+    pub fn export(&self) -> DkgResult<KeyPackage<G>> {
+        let secret_share = self.try_get_secret_share()?;
+        let verification_shares = self
+            .valid_participant_ids
+            .iter()
+            .map(|id| (*id, self.verification_share(*id)))
+            .collect();
+        Ok(KeyPackage {
+            identifier: self.id,
+            secret_share,
+            public_key: self.public_key,
+            verification_shares,
+        })
+    }
+
+    /// Convert this participant's Shamir share into an additive share
+    /// for the signing quorum `quorum`.
+    ///
+    /// The additive shares of all participants in `quorum` sum to the secret.
+    /// The share is multiplied by this participant's Lagrange coefficient for
+    /// `quorum` so the quorum is fixed at conversion time, the additive shares
+    /// cannot be combined with shares converted for any other quorum.
+    ///
+    /// Throws an error if the protocol has not completed, `quorum` has fewer than
+    /// `threshold` ids, does not contain this participant or contains an id that
+    /// is not a valid participant.
+    pub fn to_additive_share(&self, quorum: &BTreeSet<usize>) -> DkgResult<G::Scalar> {
+        let secret_share = self.try_get_secret_share()?;
+        if quorum.len() < self.threshold {
+            return Err(Error::InsufficientParticipants {
+                have: quorum.len(),
+                need: self.threshold,
+            });
+        }
+        if !quorum.contains(&self.id) {
+            return Err(Error::InvalidInput(format!(
+                "secret_participant {} is not in the quorum",
+                self.id
+            )));
+        }
+        if let Some(id) = quorum.difference(&self.valid_participant_ids).next() {
+            return Err(Error::InvalidInput(format!(
+                "secret_participant {} is not a valid participant",
+                id
+            )));
+        }
+        let ids = quorum.iter().copied().collect::<Vec<_>>();
+        let coefficients = lagrange_coefficients::<G>(&ids)?;
+        Ok(secret_share * coefficients[&self.id])
+    }
+
+    /// Derive a child key by adding the public `tweak` to the group key,
+    /// like non-hardened BIP32 derivation.
+    ///
+    /// Every participant applies the same tweak so the secret share becomes
+    /// `secret_share + tweak` and the public key `public_key + g * tweak`.
+    /// The combined commitments are updated so verification shares stay valid.
+    /// [`PublicKeyPackage::tweak_public_key`] derives the matching public key
+    /// package without any secret.
+    ///
+    /// Throws an error if the protocol has not completed or the share
+    /// was handed to a [`ShareSink`].
+    pub fn tweak_add(&mut self, tweak: G::Scalar) -> DkgResult<()> {
+        let secret_share = self.try_get_secret_share()? + tweak;
+        let offset = self.components.feldman_verifier_set.generator() * tweak;
+        self.secret_share = protect_scalar(&secret_share);
+        self.public_key += offset;
+        if let Some(constant) = self.public_commitments.first_mut() {
+            *constant += offset;
+        }
+        Ok(())
+    }
+
+    /// Add this participant's share from another completed DKG so it holds
+    /// the share of the combined key from [`aggregate_public_keys`].
+    ///
+    /// `other_public_commitments` are the combined feldman commitments of the
+    /// other DKG, see [`Participant::get_public_commitments`]. They are used to
+    /// check `other_share` and are added to this participant's commitments
+    /// so the public key and verification shares match the combined key.
+    ///
+    /// This only works when both DKGs used the same participant ids,
+    /// threshold and message generator. Every participant must add the share
+    /// of the same other DKG.
+    ///
+    /// Throws an error if the protocol has not completed, the number of
+    /// commitments does not match, or `other_share` does not match the commitments.
+    pub fn add_share_from(
+        &mut self,
+        other_share: G::Scalar,
+        other_public_commitments: &[G],
+    ) -> DkgResult<()> {
+        let secret_share = self.try_get_secret_share()?;
+        if other_public_commitments.len() != self.public_commitments.len() {
+            return Err(Error::InvalidInput(format!(
+                "expected {} commitments, received {}",
+                self.public_commitments.len(),
+                other_public_commitments.len()
+            )));
+        }
+        if self.components.feldman_verifier_set.generator() * other_share
+            != evaluate_public_polynomial(other_public_commitments, self.id)
+        {
+            return Err(Error::InvalidInput(
+                "the share does not match the commitments".to_string(),
+            ));
+        }
+        self.secret_share = protect_scalar(&(secret_share + other_share));
+        self.public_key += other_public_commitments[0];
+        for (c, o) in self
+            .public_commitments
+            .iter_mut()
+            .zip(other_public_commitments.iter())
+        {
+            *c += o;
+        }
+        Ok(())
+    }
+
+    /// Export the public key material needed to verify partial signatures.
+    ///
+    /// Throws an error if the protocol has not completed.
+    pub fn public_key_package(&self) -> DkgResult<PublicKeyPackage<G>> {
+        let public_key = self.try_get_public_key()?;
+        let verification_shares = self
+            .valid_participant_ids
+            .iter()
+            .map(|id| (*id, self.verification_share(*id)))
+            .collect();
+        Ok(PublicKeyPackage {
+            public_key,
+            verification_shares,
+            parameters: self.get_parameters(),
+        })
+    }
+
+    /// Compute the public value of the share held by `id`
+    /// from the summed feldman commitments
+    fn verification_share(&self, id: usize) -> G {
+        evaluate_public_polynomial(&self.public_commitments, id)
+    }
+
     fn lagrange_interpolation(
         share: G::Scalar,
         shares_ids: &[G::Scalar],
@@ -236,6 +1271,23 @@ where
     }
 }
 
+/// A CSPRNG seeded from the OS entropy source
+pub(crate) fn entropy_rng() -> DkgResult<ChaChaRng> {
+    seeded_rng(rand_core::OsRng)
+}
+
+/// A CSPRNG seeded from `source`.
+///
+/// The seed is drawn with `try_fill_bytes` so an unavailable entropy
+/// source is reported as [`Error::RngFailure`] instead of panicking.
+pub(crate) fn seeded_rng(mut source: impl RngCore) -> DkgResult<ChaChaRng> {
+    let mut seed = Zeroizing::new([0u8; 32]);
+    source
+        .try_fill_bytes(seed.as_mut())
+        .map_err(|e| Error::RngFailure(e.to_string()))?;
+    Ok(ChaChaRng::from_seed(*seed))
+}
+
 /// Secret Participant Implementation
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct SecretParticipantImpl<G>(PhantomData<G>);
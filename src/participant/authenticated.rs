@@ -0,0 +1,106 @@
+use super::*;
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// Set this participant's long-term `signing_key` and the verifying key
+    /// of every participant, including this one, keyed by id.
+    ///
+    /// A verifying key is `G::generator() * signing_key`.
+    /// The keys are not serialized and must be set again after deserializing.
+    ///
+    /// Throws an error if this participant's verifying key does not match `signing_key`.
+    pub fn set_authentication_keys(
+        &mut self,
+        signing_key: &G::Scalar,
+        verifying_keys: BTreeMap<usize, G>,
+    ) -> DkgResult<()> {
+        if verifying_keys.get(&self.id) != Some(&(G::generator() * signing_key)) {
+            return Err(Error::InvalidInput(format!(
+                "the verifying key for secret_participant {} does not match the signing key",
+                self.id
+            )));
+        }
+        self.signing_key = Some(protect_scalar(signing_key));
+        self.verifying_keys = verifying_keys;
+        Ok(())
+    }
+
+    /// Compute round1 for this participant and sign the broadcast data
+    /// and each peer-to-peer message with the signing key.
+    ///
+    /// Throws an error if this participant is not in round 1
+    /// or no authentication keys were set.
+    pub fn round1_authenticated(
+        &mut self,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> DkgResult<AuthenticatedRound1Output<G>> {
+        self.check_round(Round::One)?;
+        let signing_key = self
+            .signing_key
+            .as_ref()
+            .and_then(unprotect_scalar::<S, G::Scalar>)
+            .ok_or_else(|| {
+                Error::RoundError(
+                    Round::One.into(),
+                    "No authentication keys are set".to_string(),
+                )
+            })?;
+        let (bdata, p2p_data) = self.round1()?;
+        let bdata = Authenticated::sign(bdata, self.id, 0, &signing_key, &mut rng)?;
+        let mut map = BTreeMap::new();
+        for (id, data) in p2p_data {
+            map.insert(
+                id,
+                Authenticated::sign(data, self.id, id, &signing_key, &mut rng)?,
+            );
+        }
+        Ok((bdata, map))
+    }
+
+    /// Computes round2 for this participant after checking that every
+    /// message is signed by its sender's verifying key.
+    ///
+    /// Otherwise the validation is identical to [`Participant::round2`].
+    ///
+    /// Throws [`Error::BadSignature`] if any signature is invalid or
+    /// a sender has no verifying key.
+    pub fn round2_authenticated(
+        &mut self,
+        broadcast_data: BTreeMap<usize, Authenticated<Round1BroadcastData<G>, G>>,
+        p2p_data: BTreeMap<usize, Authenticated<Round1P2PData, G>>,
+    ) -> DkgResult<Round2EchoBroadcastData> {
+        self.check_round(Round::Two)?;
+        for (from, data) in &broadcast_data {
+            self.check_signature(data, *from, 0)?;
+        }
+        for (from, data) in &p2p_data {
+            self.check_signature(data, *from, self.id)?;
+        }
+        self.round2(
+            broadcast_data
+                .into_iter()
+                .map(|(id, data)| (id, data.data))
+                .collect(),
+            p2p_data
+                .into_iter()
+                .map(|(id, data)| (id, data.data.clone()))
+                .collect(),
+        )
+    }
+
+    fn check_signature<T: Serialize>(
+        &self,
+        data: &Authenticated<T, G>,
+        from: usize,
+        to: usize,
+    ) -> DkgResult<()> {
+        match self.verifying_keys.get(&from) {
+            Some(key) if data.verify(from, to, *key) => Ok(()),
+            _ => Err(Error::BadSignature { from }),
+        }
+    }
+}
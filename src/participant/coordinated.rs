@@ -0,0 +1,84 @@
+use super::*;
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// Computes round 3 for this participant in coordinated mode.
+    ///
+    /// Instead of checking the echo broadcast from every participant the
+    /// valid set signed by the coordinator configured in [`Parameters::with_coordinator`]
+    /// is used. Participants this participant did not verify in round 2 are never added.
+    ///
+    /// Throws an error if this participant is not in round 3, no coordinator
+    /// is configured, the signature is invalid, this participant is not in the
+    /// signed set or the resulting set is below the threshold, or
+    /// [`Error::Abort`] with [`AbortReason::TooManyFaults`] like [`Participant::round3`].
+    pub fn round3_coordinated(
+        &mut self,
+        signed: &SignedValidSet<G>,
+    ) -> DkgResult<Round3BroadcastData<G>> {
+        self.check_round(Round::Three)?;
+        self.check_approval()?;
+        self.check_coordinator_signature(
+            signed.verify(&self.get_parameters(), self.coordinator),
+            Round::Three,
+        )?;
+        let agreed_ids = self
+            .valid_participant_ids
+            .intersection(&signed.echo.valid_participant_ids)
+            .copied()
+            .collect::<BTreeSet<_>>();
+        self.check_max_faults(&agreed_ids)?;
+        self.notify_dropped(self.valid_participant_ids.difference(&agreed_ids).copied());
+        self.retain_valid_participants(&agreed_ids, Round::Three)?;
+        // The coordinator vouches for every participant in the signed set
+        self.contributors = self.valid_participant_ids.clone();
+
+        self.round = Round::Four;
+        self.round3_broadcast_data()
+    }
+
+    /// Computes round 5 for this participant in coordinated mode.
+    ///
+    /// Checks that the public key signed by the coordinator
+    /// matches the one computed by this participant.
+    ///
+    /// Throws an error if this participant is not in round 5, no coordinator
+    /// is configured, the signature is invalid or the public keys differ.
+    pub fn round5_coordinated(&self, signed: &SignedPublicKey<G>) -> DkgResult<()> {
+        self.check_round(Round::Five)?;
+        self.check_coordinator_signature(
+            signed.verify(&self.get_parameters(), self.coordinator),
+            Round::Five,
+        )?;
+        if signed.public_key != self.public_key {
+            return Err(Error::RoundError(
+                Round::Five.into(),
+                format!(
+                    "Public key from the coordinator does not match.  Expected {:?}, found {:?}",
+                    self.public_key, signed.public_key
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_coordinator_signature(&self, verified: bool, round: Round) -> DkgResult<()> {
+        if self.coordinator.is_identity().into() {
+            return Err(Error::RoundError(
+                round.into(),
+                "No coordinator is configured".to_string(),
+            ));
+        }
+        if !verified {
+            return Err(Error::RoundError(
+                round.into(),
+                "Invalid coordinator signature".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+impl<G, S> SecretParticipant<G, S>
+where
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// Create a participant for a new session that reshares the completed
+    /// key with the lower `threshold`, keeping the same secret and public key.
+    ///
+    /// Every participant in `quorum` calls this with the same arguments and
+    /// contributes its additive share of the secret, see
+    /// [`Participant::to_additive_share`]. The new session's parameters are
+    /// derived from `session_id` like [`ParametersBuilder::session_id`], so the
+    /// blinder generator is fresh, and all quorum members must use the same
+    /// `session_id` to get the same parameters. It should not have been used
+    /// for any earlier session. Any other participant in the new session joins
+    /// with [`RefreshParticipant::new`] using the parameters of the returned
+    /// participant so it contributes nothing to the secret. The limit, message
+    /// generator, coordinator, maximum faults and ids are unchanged.
+    ///
+    /// Throws an error if the protocol has not completed, `threshold` is not
+    /// lower than the current threshold or is less than 2, or `quorum` is not
+    /// valid for [`Participant::to_additive_share`].
+    pub fn downgrade_threshold(
+        &self,
+        threshold: NonZeroUsize,
+        quorum: &BTreeSet<usize>,
+        session_id: &[u8],
+    ) -> DkgResult<Self> {
+        if threshold.get() >= self.threshold {
+            return Err(Error::InvalidInput(format!(
+                "the new threshold {} must be lower than the current threshold {}",
+                threshold, self.threshold
+            )));
+        }
+        let secret = self.to_additive_share(quorum)?;
+        let current = self.get_parameters();
+        let mut builder = ParametersBuilder::new()
+            .threshold(threshold.get())
+            .limit(current.limit)
+            .message_generator(current.message_generator)
+            .session_id(session_id);
+        if let Some(coordinator) = current.coordinator() {
+            builder = builder.coordinator(coordinator);
+        }
+        if let Some(max_faults) = current.max_faults() {
+            builder = builder.max_faults(max_faults);
+        }
+        let parameters = builder.build()?;
+        let id = NonZeroUsize::new(self.id)
+            .ok_or_else(|| Error::InitializationError("The participant id is zero".to_string()))?;
+        let mut rng = entropy_rng()?;
+        let blinder = G::Scalar::random(&mut rng);
+        Self::initialize(id, parameters, secret, blinder, rng)
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, KeyInit, Nonce};
+
+const ECIES_DOMAIN: &[u8] = b"gennaro-dkg ecies share encryption";
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// Compute round1 for this participant encrypting each peer-to-peer
+    /// message to the recipient's long-term public key.
+    ///
+    /// `encryption_keys` maps each participant id to its public key
+    /// `G::generator() * decryption_key`. The broadcast data is unchanged.
+    ///
+    /// Throws an error if this participant is not in round 1 or
+    /// if a recipient has no key in `encryption_keys`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "encrypted-shares")))]
+    pub fn round1_encrypted(
+        &mut self,
+        encryption_keys: &BTreeMap<usize, G>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> DkgResult<EncryptedRound1Output<G>> {
+        self.check_round(Round::One)?;
+        for id in (1..=self.limit).filter(|id| *id != self.id) {
+            match encryption_keys.get(&id) {
+                None => {
+                    return Err(Error::InvalidInput(format!(
+                        "no encryption key for participant {}",
+                        id
+                    )))
+                }
+                Some(key) if bool::from(key.is_identity()) => {
+                    return Err(Error::InvalidInput(format!(
+                        "the encryption key for participant {} is the identity",
+                        id
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        let (bdata, p2p_data) = self.round1()?;
+        let mut map = BTreeMap::new();
+        for (id, data) in p2p_data {
+            let ephemeral = G::Scalar::random(&mut rng);
+            let ephemeral_key = G::generator() * ephemeral;
+            let shared = encryption_keys[&id] * ephemeral;
+            let mut plaintext = Zeroizing::new(data.secret_share.clone());
+            plaintext.extend_from_slice(&data.blind_share);
+            let ciphertext = ecies_cipher(ephemeral_key, shared, id)
+                .encrypt(&Nonce::default(), plaintext.as_slice())
+                .map_err(|_| Error::SerializationError("encryption failed".to_string()))?;
+            map.insert(
+                id,
+                EncryptedRound1P2PData {
+                    ephemeral_key,
+                    ciphertext,
+                },
+            );
+        }
+        Ok((bdata, map))
+    }
+
+    /// Computes round2 for this participant after decrypting the
+    /// peer-to-peer data with this participant's long-term `decryption_key`.
+    ///
+    /// Data that does not decrypt is treated the same as an invalid share
+    /// and the sender is not included in the valid participants.
+    /// Otherwise the validation is identical to [`Participant::round2`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "encrypted-shares")))]
+    pub fn round2_encrypted(
+        &mut self,
+        decryption_key: &G::Scalar,
+        mut broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+        p2p_data: BTreeMap<usize, EncryptedRound1P2PData<G>>,
+    ) -> DkgResult<Round2EchoBroadcastData> {
+        self.check_round(Round::Two)?;
+        let mut decrypted = BTreeMap::new();
+        for (id, data) in &p2p_data {
+            let shared = data.ephemeral_key * decryption_key;
+            let plaintext = ecies_cipher(data.ephemeral_key, shared, self.id)
+                .decrypt(&Nonce::default(), data.ciphertext.as_slice())
+                .map(Zeroizing::new);
+            match plaintext {
+                Ok(plaintext) if plaintext.len() % 2 == 0 => {
+                    let (secret_share, blind_share) = plaintext.split_at(plaintext.len() / 2);
+                    decrypted.insert(
+                        *id,
+                        Round1P2PData {
+                            secret_share: secret_share.to_vec(),
+                            blind_share: blind_share.to_vec(),
+                        },
+                    );
+                }
+                _ => {
+                    broadcast_data.remove(id);
+                }
+            }
+        }
+        self.round2(broadcast_data, decrypted)
+    }
+}
+
+/// Derive the cipher for a share sent to `recipient` from the
+/// ephemeral public key and the Diffie-Hellman shared point.
+///
+/// A fresh ephemeral key is used for every message so the key is
+/// never reused and a fixed nonce is safe.
+fn ecies_cipher<G: Group + GroupEncoding>(
+    ephemeral_key: G,
+    shared: G,
+    recipient: usize,
+) -> ChaCha20Poly1305 {
+    let key = Zeroizing::new(Sha256Hasher::digest(&[
+        ECIES_DOMAIN,
+        ephemeral_key.to_bytes().as_ref(),
+        shared.to_bytes().as_ref(),
+        &(recipient as u64).to_be_bytes(),
+    ]));
+    ChaCha20Poly1305::new((&*key).into())
+}
@@ -0,0 +1,53 @@
+use super::*;
+use chacha20poly1305::{aead::Aead, KeyInit, XChaCha20Poly1305, XNonce};
+use serde::de::DeserializeOwned;
+
+const NONCE_LENGTH: usize = 24;
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+    Self: Serialize + DeserializeOwned,
+{
+    /// Serialize this participant and encrypt it with XChaCha20-Poly1305 under `key`
+    /// so the secret share never reaches storage in plaintext.
+    ///
+    /// The output is the random 24 byte nonce followed by the ciphertext.
+    #[cfg_attr(docsrs, doc(cfg(feature = "encrypted-storage")))]
+    pub fn to_encrypted_bytes(&self, key: &[u8; 32]) -> DkgResult<Vec<u8>> {
+        let plaintext = Zeroizing::new(
+            serde_bare::to_vec(self).map_err(|e| Error::SerializationError(e.to_string()))?,
+        );
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand_core::OsRng.fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(key.into())
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| Error::SerializationError("encryption failed".to_string()))?;
+        let mut output = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+        output.extend_from_slice(&nonce);
+        output.extend_from_slice(&ciphertext);
+        Ok(output)
+    }
+
+    /// Decrypt and deserialize a participant created with [`Participant::to_encrypted_bytes`].
+    ///
+    /// Throws an error if `key` is wrong or the bytes have been modified.
+    #[cfg_attr(docsrs, doc(cfg(feature = "encrypted-storage")))]
+    pub fn from_encrypted_bytes(key: &[u8; 32], bytes: &[u8]) -> DkgResult<Self> {
+        if bytes.len() < NONCE_LENGTH {
+            return Err(Error::SerializationError(
+                "encrypted bytes are too short".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LENGTH);
+        let plaintext = Zeroizing::new(
+            XChaCha20Poly1305::new(key.into())
+                .decrypt(XNonce::from_slice(nonce), ciphertext)
+                .map_err(|_| Error::SerializationError("decryption failed".to_string()))?,
+        );
+        serde_bare::from_slice(plaintext.as_slice())
+            .map_err(|e| Error::SerializationError(e.to_string()))
+    }
+}
@@ -0,0 +1,122 @@
+use super::*;
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// Start enrolling a new participant `new_id` with the help of the
+    /// completed participants in `quorum`, which must include this participant.
+    ///
+    /// This participant's part of the new share is split into random masks,
+    /// one for each member of `quorum` keyed by their id. Send each mask to its
+    /// member over a private channel and keep the one for this participant.
+    /// No single mask reveals anything about this participant's share.
+    ///
+    /// Throws an error if the protocol has not completed, `quorum` is smaller
+    /// than the threshold, or `new_id` is zero or already in `quorum`.
+    pub fn enrollment_masks(
+        &self,
+        new_id: usize,
+        quorum: &BTreeSet<usize>,
+    ) -> DkgResult<BTreeMap<usize, G::Scalar>> {
+        let secret_share = self.try_get_secret_share()?;
+        if new_id == 0 || quorum.contains(&new_id) {
+            return Err(Error::InvalidInput(format!(
+                "cannot enroll secret_participant {}",
+                new_id
+            )));
+        }
+        if !quorum.contains(&self.id) {
+            return Err(Error::InvalidInput(format!(
+                "secret_participant {} is not in the quorum",
+                self.id
+            )));
+        }
+        if quorum.len() < self.threshold {
+            return Err(Error::InsufficientParticipants {
+                have: quorum.len(),
+                need: self.threshold,
+            });
+        }
+
+        let ids = quorum.iter().copied().collect::<Vec<_>>();
+        let coefficients = lagrange_coefficients_at::<G>(&ids, new_id)?;
+        let mut remaining = secret_share * coefficients[&self.id];
+        let mut rng = entropy_rng()?;
+        let mut masks = BTreeMap::new();
+        for id in quorum.iter().skip(1) {
+            let mask = G::Scalar::random(&mut rng);
+            remaining -= mask;
+            masks.insert(*id, mask);
+        }
+        masks.insert(ids[0], remaining);
+        Ok(masks)
+    }
+
+    /// Sum the masks received from every member of the quorum, including
+    /// the one this participant kept, into the value to send to the new participant.
+    pub fn enrollment_share_for(
+        &self,
+        new_id: usize,
+        masks: &BTreeMap<usize, G::Scalar>,
+    ) -> DkgResult<G::Scalar> {
+        if new_id == 0 || new_id == self.id {
+            return Err(Error::InvalidInput(format!(
+                "cannot enroll secret_participant {}",
+                new_id
+            )));
+        }
+        if masks.len() < self.threshold {
+            return Err(Error::InsufficientParticipants {
+                have: masks.len(),
+                need: self.threshold,
+            });
+        }
+        Ok(masks.values().sum())
+    }
+
+    /// Create a completed participant with id `new_id` from the values
+    /// computed by [`Participant::enrollment_share_for`] by each quorum member.
+    ///
+    /// `public_commitments` are the group's commitments from
+    /// [`Participant::get_public_commitments`] and are used to verify the new share.
+    ///
+    /// Throws an error if the new share does not match the commitments.
+    pub fn from_enrollment(
+        new_id: NonZeroUsize,
+        parameters: Parameters<G>,
+        public_commitments: &[G],
+        contributions: &BTreeMap<usize, G::Scalar>,
+    ) -> DkgResult<Self> {
+        if public_commitments.len() != parameters.threshold {
+            return Err(Error::InvalidInput(
+                "the number of public commitments must equal the threshold".to_string(),
+            ));
+        }
+        if contributions.len() < parameters.threshold {
+            return Err(Error::InsufficientParticipants {
+                have: contributions.len(),
+                need: parameters.threshold,
+            });
+        }
+        let secret_share = contributions.values().sum::<G::Scalar>();
+        if parameters.message_generator * secret_share
+            != evaluate_public_polynomial(public_commitments, new_id.get())
+        {
+            return Err(Error::InitializationError(
+                "The enrolled share does not match the public commitments".to_string(),
+            ));
+        }
+
+        let mut participant = Self::new(new_id, parameters)?;
+        participant.secret_share = protect_scalar(&secret_share);
+        participant.public_key = public_commitments[0];
+        participant.public_commitments = public_commitments.to_vec();
+        participant.valid_participant_ids = contributions.keys().copied().collect();
+        participant.valid_participant_ids.insert(new_id.get());
+        participant.round = Round::Five;
+        Ok(participant)
+    }
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+impl<G, S> RefreshParticipant<G, S>
+where
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// The additive delta for this participant's share after a completed refresh.
+    ///
+    /// Every refresh participant shares zero so the combined share is the
+    /// delta to add to the existing share: `new_share = old_share + delta`.
+    /// This allows applying the refresh to a share stored outside of `Participant`.
+    ///
+    /// Throws [`Error::NotComplete`] if the refresh has not completed,
+    /// an error if the combined commitments do not share zero, or
+    /// [`Error::ShareSelfCheckFailed`] if `g^delta` does not match the
+    /// combined refresh commitments.
+    pub fn refresh_delta(&self) -> DkgResult<G::Scalar> {
+        let delta = self.try_get_secret_share()?;
+        if self
+            .public_commitments
+            .first()
+            .is_none_or(|c| c.is_identity().unwrap_u8() == 0u8)
+        {
+            return Err(Error::RoundError(
+                Round::Five.into(),
+                "The refresh commitments do not share zero".to_string(),
+            ));
+        }
+        if self.components.feldman_verifier_set.generator() * delta
+            != evaluate_public_polynomial(&self.public_commitments, self.id)
+        {
+            return Err(Error::ShareSelfCheckFailed);
+        }
+        Ok(delta)
+    }
+}
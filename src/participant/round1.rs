@@ -1,18 +1,21 @@
 use super::*;
 
-impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
     /// Compute round1 for this participant.
     ///
+    /// With the `signed-echo` feature a random echo key is chosen
+    /// and its public key is added to the broadcast data.
+    ///
     /// Throws an error if this participant is not in round 1.
     pub fn round1(
         &mut self,
     ) -> DkgResult<(Round1BroadcastData<G>, BTreeMap<usize, Round1P2PData>)> {
-        if !matches!(self.round, Round::One) {
-            return Err(Error::RoundError(
-                Round::One.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
-        }
+        self.check_round(Round::One)?;
         let mut map = BTreeMap::new();
         for (s, b) in self
             .components
@@ -34,6 +37,13 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             );
         }
 
+        #[cfg(feature = "signed-echo")]
+        let echo_key = {
+            let echo_key = G::Scalar::random(entropy_rng()?);
+            self.echo_key = protect_scalar(&echo_key);
+            G::generator() * echo_key
+        };
+
         self.round = Round::Two;
         let bdata = Round1BroadcastData {
             blinder_generator: self.components.pedersen_verifier_set.blinder_generator(),
@@ -43,8 +53,18 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                 .pedersen_verifier_set
                 .blind_verifiers()
                 .to_vec(),
+            #[cfg(feature = "signed-echo")]
+            echo_key,
         };
 
         Ok((bdata, map))
     }
+
+    /// Compute round1 for this participant returning named outputs
+    /// instead of a tuple.
+    ///
+    /// Throws an error if this participant is not in round 1.
+    pub fn round1_output(&mut self) -> DkgResult<Round1Output<G>> {
+        self.round1().map(Round1Output::from)
+    }
 }
@@ -1,6 +1,11 @@
 use super::*;
 
-impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
     /// Computes round2 for this participant.
     ///
     /// Inputs correspond to messages received from other participants
@@ -21,7 +26,16 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
     ///     4: Round1P2PData, // from participant 4
     /// }
     ///
+    /// A participant that is only in one of `broadcast_data` and `p2p_data`
+    /// is excluded from the valid set like a participant whose data failed
+    /// verification, see [`Participant::missing_round1_data`].
+    ///
     /// Throws an error if this participant is not in round 2.
+    /// [`Error::InvalidCommitment`] is returned if a broadcast does not
+    /// contain exactly `threshold` pedersen commitments and
+    /// [`Error::DuplicateCommitments`] if participants sent identical commitments.
+    /// If too few participants remain after dropping the ones whose shares
+    /// failed verification, [`Error::MultipleBadDealers`] lists all of them.
     ///
     /// Returns the data needed for round 2
     pub fn round2(
@@ -29,11 +43,9 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
         broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
         p2p_data: BTreeMap<usize, Round1P2PData>,
     ) -> DkgResult<Round2EchoBroadcastData> {
-        if !matches!(self.round, Round::Two) {
-            return Err(Error::RoundError(
-                Round::Two.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
+        self.check_round(Round::Two)?;
+        if self.verifier_only {
+            return self.round2_verifier(broadcast_data);
         }
 
         if broadcast_data.is_empty() {
@@ -71,100 +83,309 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             ));
         }
 
-        self.valid_participant_ids.clear();
+        self.check_broadcasts(&broadcast_data)?;
+
+        // Nothing is changed until all the data has been checked
+        // so round 2 can be retried with corrected data
+        let mut valid_participant_ids = BTreeSet::new();
         let mut secret_share =
             self.components.secret_shares[self.id - 1].as_field_element::<G::Scalar>()?;
         let og = secret_share;
 
-        // Create a unique list of secret_participant ids
-        let pids = broadcast_data
+        for (pid, bdata) in &broadcast_data {
+            // Data that arrived over only one channel is skipped, the sender
+            // probably did not receive it and is not necessarily malicious
+            let p2p = match p2p_data.get(pid) {
+                Some(p2p) => p2p,
+                None => continue,
+            };
+            if let Ok(s) = self.check_dealer(*pid, bdata, p2p) {
+                secret_share += s;
+                valid_participant_ids.insert(*pid);
+            }
+        }
+
+        let bad_dealers = broadcast_data
             .keys()
+            .filter(|id| !valid_participant_ids.contains(id))
             .copied()
-            .chain(p2p_data.keys().copied())
-            .collect::<BTreeSet<usize>>();
-        for pid in &pids {
-            // resolve bid != pid where bid might exist or pid might exist in the other
-            // probably didn't receive the data, not necessarily malicious
-            let opt_bdata = broadcast_data.get(pid);
-            if opt_bdata.is_none() {
-                continue;
-            }
-            let opt_p2p_data = p2p_data.get(pid);
-            if opt_p2p_data.is_none() {
-                continue;
-            }
+            .collect::<BTreeSet<_>>();
+        self.notify_dropped(
+            bad_dealers.iter().copied().chain(
+                p2p_data
+                    .keys()
+                    .filter(|id| !broadcast_data.contains_key(id))
+                    .copied(),
+            ),
+        );
+        // Report every bad dealer at once so they can all be removed before retrying
+        if !bad_dealers.is_empty()
+            && self.threshold < self.limit
+            && valid_participant_ids.len() + 1 < self.threshold
+        {
+            return Err(Error::MultipleBadDealers(bad_dealers));
+        }
 
-            let bdata = opt_bdata.unwrap();
+        if secret_share.is_zero().into() || secret_share == og {
+            return Err(Error::RoundError(
+                Round::Two.into(),
+                "The resulting secret key share is invalid".to_string(),
+            ));
+        }
+        // Include own id in valid set
+        valid_participant_ids.insert(self.id);
+        self.check_valid_participants(&valid_participant_ids)?;
+        let echo_data = Round2EchoBroadcastData {
+            valid_participant_ids: valid_participant_ids.clone(),
+            #[cfg(feature = "signed-echo")]
+            signature: Some(self.sign_echo(&valid_participant_ids)?),
+        };
 
-            // If not using the same generator then its a problem
-            if bdata.blinder_generator != self.components.pedersen_verifier_set.blinder_generator()
-                || bdata.message_generator
-                    != self.components.pedersen_verifier_set.secret_generator()
-                || bdata.pedersen_commitments.len() != self.threshold
-            {
-                continue;
-            }
+        self.round = Round::Three;
+        self.contributors = broadcast_data.keys().copied().collect();
+        self.valid_participant_ids = valid_participant_ids;
+        self.round1_p2p_data = p2p_data
+            .iter()
+            .map(|(key, value)| (*key, protect_p2p(value)))
+            .collect();
+        self.round1_broadcast_data = broadcast_data;
 
-            if bdata
-                .pedersen_commitments
-                .iter()
-                .any(|c| c.is_identity().into())
-            {
-                continue;
-            }
-            let p2p = opt_p2p_data.unwrap();
-            if (p2p.secret_share.is_zero() | p2p.blind_share.is_zero()).into() {
-                continue;
-            }
+        self.secret_share = protect_scalar(&secret_share);
 
-            let verifier = Vec::<G>::pedersen_set_with_generators_and_verifiers(
-                bdata.message_generator,
-                bdata.blinder_generator,
-                &bdata.pedersen_commitments,
-            );
-
-            if verifier
-                .verify_share_and_blinder(&p2p.secret_share, &p2p.blind_share)
-                .is_err()
-            {
-                continue;
-            }
-            if let Ok(s) = p2p.secret_share.as_field_element::<G::Scalar>() {
-                secret_share += s;
-                self.valid_participant_ids.insert(*pid);
-            }
+        // The blinder and its shares are not needed after round 1 was verified
+        for share in self.components.blinder_shares.iter_mut() {
+            share.zeroize();
         }
+        self.components.blinder_shares = Vec::new();
+        self.components.blinder = G::Scalar::ZERO;
 
-        if secret_share.is_zero().into() || secret_share == og {
+        Ok(echo_data)
+    }
+
+    /// Check the round 1 data from a single dealer `from` as soon as
+    /// both the broadcast and the peer-to-peer data have arrived.
+    ///
+    /// This performs the same checks as [`Participant::round2`] does for each
+    /// dealer so invalid data is noticed during the receive window and
+    /// the dealer can be excluded before round 2 is computed. Round 2 still
+    /// checks all inputs because it cannot trust that they were verified.
+    ///
+    /// Throws an error if this participant is not in round 1 or 2,
+    /// [`Error::ParametersMismatch`] if the dealer used different parameters,
+    /// [`Error::InvalidCommitment`] if the commitments are malformed, or
+    /// [`Error::RoundError`] if the shares do not match the commitments.
+    pub fn verify_incoming(
+        &self,
+        from: usize,
+        broadcast: &Round1BroadcastData<G>,
+        p2p: &Round1P2PData,
+    ) -> DkgResult<()> {
+        if !matches!(self.round, Round::One) {
+            self.check_round(Round::Two)?;
+        }
+        if from == self.id {
             return Err(Error::RoundError(
                 Round::Two.into(),
-                "The resulting secret key share is invalid".to_string(),
+                "Cannot verify data from this participant".to_string(),
             ));
         }
-        self.valid_participant_ids.insert(self.id);
-        if self.valid_participant_ids.len() < self.threshold {
+        self.check_dealer(from, broadcast, p2p).map(|_| ())
+    }
+
+    /// Round 2 for a verifier-only participant which only
+    /// has the broadcast data to check
+    fn round2_verifier(
+        &mut self,
+        broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+    ) -> DkgResult<Round2EchoBroadcastData> {
+        if broadcast_data.len() < self.threshold {
             return Err(Error::RoundError(
                 Round::Two.into(),
-                "Not enough valid participants, below the threshold".to_string(),
+                format!(
+                    "Not enough secret_participant data. Expected {}, received {}",
+                    self.threshold,
+                    broadcast_data.len()
+                ),
             ));
         }
+        self.check_broadcasts(&broadcast_data)?;
 
-        self.round = Round::Three;
-        // Include own id in valid set
-        self.round1_p2p_data = p2p_data
+        let valid_participant_ids = broadcast_data
             .iter()
-            .map(|(key, value)| {
-                let val = Arc::new(Mutex::new(Protected::serde(value).unwrap()));
-                (*key, val)
-            })
-            .collect();
+            .filter(|(id, bdata)| self.check_dealer_commitments(**id, bdata).is_ok())
+            .map(|(id, _)| *id)
+            .collect::<BTreeSet<_>>();
+        self.notify_dropped(
+            broadcast_data
+                .keys()
+                .filter(|id| !valid_participant_ids.contains(id))
+                .copied(),
+        );
+        self.check_valid_participants(&valid_participant_ids)?;
+        let echo_data = Round2EchoBroadcastData {
+            valid_participant_ids: valid_participant_ids.clone(),
+            #[cfg(feature = "signed-echo")]
+            signature: None,
+        };
+
+        self.round = Round::Three;
+        self.contributors = broadcast_data.keys().copied().collect();
+        self.valid_participant_ids = valid_participant_ids;
         self.round1_broadcast_data = broadcast_data;
+        Ok(echo_data)
+    }
 
-        let echo_data = Round2EchoBroadcastData {
-            valid_participant_ids: self.valid_participant_ids.clone(),
+    /// Check the commitment vectors have the right length and are all distinct
+    fn check_broadcasts(
+        &self,
+        broadcast_data: &BTreeMap<usize, Round1BroadcastData<G>>,
+    ) -> DkgResult<()> {
+        // A commitment vector of the wrong length describes a polynomial
+        // of the wrong degree
+        if let Some(from) = broadcast_data
+            .iter()
+            .find(|(_, bdata)| bdata.pedersen_commitments.len() != self.threshold)
+            .map(|(id, _)| *id)
+        {
+            return Err(Error::InvalidCommitment { from });
+        }
+
+        // A participant that copies another's commitments
+        // contributes no randomness of its own
+        let own_commitments = self.components.pedersen_verifier_set.blind_verifiers();
+        let mut senders = BTreeMap::<Vec<u8>, BTreeSet<usize>>::new();
+        for (id, commitments) in broadcast_data
+            .iter()
+            .map(|(id, bdata)| (*id, bdata.pedersen_commitments.as_slice()))
+            .chain((!self.verifier_only).then_some((self.id, own_commitments)))
+        {
+            let encoding = commitments
+                .iter()
+                .flat_map(|c| c.to_bytes().as_ref().to_vec())
+                .collect::<Vec<u8>>();
+            senders.entry(encoding).or_default().insert(id);
+        }
+        if let Some(ids) = senders.into_values().find(|ids| ids.len() > 1) {
+            return Err(Error::DuplicateCommitments { ids });
+        }
+        Ok(())
+    }
+
+    /// Verify the shares from dealer `from` against its commitments
+    /// and return the secret share
+    fn check_dealer(
+        &self,
+        from: usize,
+        bdata: &Round1BroadcastData<G>,
+        p2p: &Round1P2PData,
+    ) -> DkgResult<G::Scalar> {
+        self.check_dealer_commitments(from, bdata)?;
+        let invalid_share = || {
+            Error::RoundError(
+                Round::Two.into(),
+                format!("Invalid share from secret_participant {}", from),
+            )
         };
-        self.secret_share = Arc::new(Mutex::new(Protected::field_element(secret_share)));
+        if (p2p.secret_share.is_zero() | p2p.blind_share.is_zero()).into() {
+            return Err(invalid_share());
+        }
 
+        if !verify_share(
+            &p2p.secret_share,
+            &p2p.blind_share,
+            &bdata.pedersen_commitments,
+            self.id,
+            bdata.message_generator,
+            bdata.blinder_generator,
+        ) {
+            return Err(invalid_share());
+        }
+        p2p.secret_share
+            .as_field_element::<G::Scalar>()
+            .map_err(|_| invalid_share())
+    }
+
+    /// Check dealer `from` used the same parameters and sent no identity commitments
+    fn check_dealer_commitments(
+        &self,
+        from: usize,
+        bdata: &Round1BroadcastData<G>,
+    ) -> DkgResult<()> {
+        // If not using the same parameters then its a problem
+        let parameters = self.get_parameters();
+        let advertised = Parameters {
+            threshold: bdata.pedersen_commitments.len(),
+            limit: self.limit,
+            message_generator: bdata.message_generator,
+            blinder_generator: bdata.blinder_generator,
+            coordinator: parameters.coordinator,
+            max_faults: parameters.max_faults,
+        };
+        parameters.validate_against(&advertised)?;
+
+        if bdata
+            .pedersen_commitments
+            .iter()
+            .any(|c| c.is_identity().into())
+        {
+            return Err(Error::InvalidCommitment { from });
+        }
+        Ok(())
+    }
+
+    /// Sign the valid set with the echo key chosen in round 1
+    #[cfg(feature = "signed-echo")]
+    fn sign_echo(&self, valid_participant_ids: &BTreeSet<usize>) -> DkgResult<Vec<u8>> {
+        let echo_key = unprotect_scalar::<S, G::Scalar>(&self.echo_key).ok_or_else(|| {
+            Error::RoundError(
+                Round::Two.into(),
+                "invalid echo key unprotected".to_string(),
+            )
+        })?;
+        echo_signature::sign_echo::<G>(self.id, valid_participant_ids, &echo_key, entropy_rng()?)
+    }
+
+    /// Store peer-to-peer data received from participant `from`
+    /// over a channel established before the broadcast channel.
+    ///
+    /// The data is not checked until [`Participant::round2_with_preloaded`]
+    /// is called. Preloading data from the same participant again
+    /// replaces the previous data.
+    ///
+    /// Throws an error if this participant has already completed round 2
+    /// or if `from` is this participant's id.
+    pub fn preload_p2p(&mut self, from: usize, data: Round1P2PData) -> DkgResult<()> {
+        if !matches!(self.round, Round::One) {
+            self.check_round(Round::Two)?;
+        }
+        if from == self.id {
+            return Err(Error::RoundError(
+                Round::Two.into(),
+                "Cannot preload peer-to-peer data from this participant".to_string(),
+            ));
+        }
+        self.preloaded_p2p_data.insert(from, protect_p2p(&data));
+        self.contributors.insert(from);
+        Ok(())
+    }
+
+    /// Computes round2 for this participant using the peer-to-peer data
+    /// stored with [`Participant::preload_p2p`].
+    ///
+    /// The validation is identical to [`Participant::round2`].
+    pub fn round2_with_preloaded(
+        &mut self,
+        broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+    ) -> DkgResult<Round2EchoBroadcastData> {
+        let mut p2p_data = BTreeMap::new();
+        for (id, data) in &self.preloaded_p2p_data {
+            let data = unprotect_p2p(data).ok_or_else(|| {
+                Error::RoundError(Round::Two.into(), "invalid secret unprotected".to_string())
+            })?;
+            p2p_data.insert(*id, data);
+        }
+        let echo_data = self.round2(broadcast_data, p2p_data)?;
+        self.preloaded_p2p_data.clear();
         Ok(echo_data)
     }
 }
@@ -1,26 +1,43 @@
 use super::*;
 
-impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
     /// Computes round 3 for this participant.
     ///
     /// This round checks for valid participant ids to make
     /// sure this participant reached the same decision
     /// as all honest participants.
     ///
-    /// If all reported ids match this participants expectations
-    /// the round will succeed and continue to the next round.
+    /// If the reported sets differ the participants deterministically agree on
+    /// the intersection of the sets echoed by the participants in this
+    /// participant's valid set. The round succeeds and continues to the next
+    /// round if the intersection contains this participant and still has at
+    /// least `threshold` participants. The contributions of any participant
+    /// outside the intersection are removed from the secret share.
     ///
-    /// Throws an error if this participant is not in round 3.
+    /// With the `signed-echo` feature the echo of every participant in the
+    /// valid set must be signed with the echo key it sent in round 1.
+    ///
+    /// A verifier-only participant only narrows its valid set, the returned
+    /// data contains no commitments and does not need to be sent.
+    ///
+    /// Throws an error if this participant is not in round 3, if an echo
+    /// signature is missing or invalid, if the intersection
+    /// excludes this participant or is below the threshold,
+    /// [`Error::Abort`] with [`AbortReason::TooManyFaults`] if more participants
+    /// than [`Parameters::max_faults`] are outside the intersection, or
+    /// [`Error::AwaitingApproval`] if approval is required and not given yet,
+    /// see [`Participant::require_valid_set_approval`].
     pub fn round3(
         &mut self,
         echo_data: &BTreeMap<usize, Round2EchoBroadcastData>,
     ) -> DkgResult<Round3BroadcastData<G>> {
-        if !matches!(self.round, Round::Three) {
-            return Err(Error::RoundError(
-                Round::Three.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
-        }
+        self.check_round(Round::Three)?;
+        self.check_approval()?;
 
         if echo_data.is_empty() {
             return Err(Error::RoundError(
@@ -35,6 +52,10 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             ));
         }
 
+        // Participants can disagree about the valid set, for example when a
+        // complaint arrives late, so all of them continue with the intersection
+        // of the valid sets echoed by the participants in their own valid set
+        let mut agreed_ids = self.valid_participant_ids.clone();
         for (id, echo) in echo_data {
             if self.id == *id {
                 continue;
@@ -42,27 +63,115 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             if !self.valid_participant_ids.contains(id) {
                 continue;
             }
-            if self
-                .valid_participant_ids
-                .difference(&echo.valid_participant_ids)
-                .count()
-                != 0
+            agreed_ids.retain(|i| echo.valid_participant_ids.contains(i));
+            #[cfg(feature = "signed-echo")]
+            if !self
+                .round1_broadcast_data
+                .get(id)
+                .is_some_and(|round1| echo.verify_signature(*id, round1))
             {
                 return Err(Error::RoundError(
                     Round::Three.into(),
                     format!(
-                        "Received data from malicious secret_participant {}. Valid sets don't match.",
+                        "Received a missing or invalid echo signature from secret_participant {}",
                         *id
                     ),
                 ));
             }
         }
 
-        let round3_bdata = Round3BroadcastData {
-            commitments: self.components.feldman_verifier_set.verifiers().to_vec(),
-        };
+        self.check_max_faults(&agreed_ids)?;
+        self.notify_dropped(self.valid_participant_ids.difference(&agreed_ids).copied());
+        if self.verifier_only {
+            // There are no contributions to drop and this participant
+            // is never part of the valid set
+            self.check_valid_participants(&agreed_ids)?;
+            self.valid_participant_ids = agreed_ids;
+        } else {
+            // Drops the contributions of participants outside the intersection
+            // and fails if it excludes this participant or is below the threshold
+            self.retain_valid_participants(&agreed_ids, Round::Three)?;
+        }
+
+        let round3_bdata = self.round3_broadcast_data()?;
         self.round = Round::Four;
+        self.contributors = echo_data.keys().copied().collect();
 
         Ok(round3_bdata)
     }
+
+    /// Store the round 2 echo broadcast received from participant `id`
+    /// until [`Participant::finalize_round3`] is called.
+    ///
+    /// Echoes can be added in any order as they arrive. Adding an echo
+    /// from the same participant again replaces the previous one.
+    ///
+    /// Throws an error if this participant is not in round 3.
+    pub fn add_round2_echo(&mut self, id: usize, data: Round2EchoBroadcastData) -> DkgResult<()> {
+        self.check_round(Round::Three)?;
+        self.round2_echo_data.insert(id, data);
+        Ok(())
+    }
+
+    /// Computes round 3 with the echoes stored by [`Participant::add_round2_echo`].
+    ///
+    /// The result is identical to calling [`Participant::round3`] with the same echoes.
+    ///
+    /// Throws an error if an echo from a valid participant other than this one
+    /// is missing, otherwise the same errors as [`Participant::round3`].
+    pub fn finalize_round3(&mut self) -> DkgResult<Round3BroadcastData<G>> {
+        self.check_round(Round::Three)?;
+        if let Some(id) = self
+            .valid_participant_ids
+            .iter()
+            .find(|id| **id != self.id && !self.round2_echo_data.contains_key(id))
+        {
+            return Err(Error::RoundError(
+                Round::Three.into(),
+                format!("Missing echo data from secret_participant {}", id),
+            ));
+        }
+        let echo_data = std::mem::take(&mut self.round2_echo_data);
+        let result = self.round3(&echo_data);
+        if result.is_err() {
+            self.round2_echo_data = echo_data;
+        }
+        result
+    }
+
+    /// Reconcile this participant's valid set with the round 2 echo of a
+    /// peer that advanced on the other side of a network partition.
+    ///
+    /// If the sets are equal, or the peer's set contains this participant's
+    /// set, [`ReconcileOutcome::NoChange`] is returned. Otherwise the valid set
+    /// is narrowed to the intersection and [`ReconcileOutcome::Merged`] is
+    /// returned if it contains this participant and at least `threshold`
+    /// participants. The contributions of the removed participants are
+    /// removed from the secret share as in [`Participant::round3`].
+    /// If the intersection is unusable nothing is changed and
+    /// [`ReconcileOutcome::AbortRequired`] is returned.
+    ///
+    /// Throws an error if this participant is not in round 3 or 4.
+    pub fn reconcile(
+        &mut self,
+        peer_echo: &Round2EchoBroadcastData,
+    ) -> DkgResult<ReconcileOutcome> {
+        if self.round != Round::Four {
+            self.check_round(Round::Three)?;
+        }
+        let agreed_ids = self
+            .valid_participant_ids
+            .intersection(&peer_echo.valid_participant_ids)
+            .copied()
+            .collect::<BTreeSet<_>>();
+        if agreed_ids == self.valid_participant_ids {
+            return Ok(ReconcileOutcome::NoChange);
+        }
+        if !agreed_ids.contains(&self.id) || agreed_ids.len() < self.threshold {
+            return Ok(ReconcileOutcome::AbortRequired);
+        }
+        self.notify_dropped(self.valid_participant_ids.difference(&agreed_ids).copied());
+        self.retain_valid_participants(&agreed_ids, self.round)?;
+        Ok(ReconcileOutcome::Merged)
+    }
 }
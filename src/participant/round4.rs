@@ -1,7 +1,11 @@
 use super::*;
-use std::ops::Deref;
 
-impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
     /// Computes round 4 for this participant.
     ///
     /// Receives the associated feldman verifiers and checks the secret
@@ -10,18 +14,31 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
     /// If they all pass then, the public key and secret key share are returned
     ///
     /// The public key should be echo broadcast to all valid participants to be checked.
+    /// It is the sum of the valid participants' constant term commitments so it
+    /// does not depend on the order the data is received or inserted.
     ///
-    /// Throws an error if this participant is not in round 4.
+    /// A participant that repeats different pedersen commitments than the ones
+    /// received in round 1, or whose commitments do not verify the share it sent
+    /// this participant, is dropped from the valid set and its share is removed
+    /// from this participant's secret share.
+    ///
+    /// Throws an error if this participant is not in round 4, if fewer than
+    /// `threshold` valid participants remain,
+    /// if a participant sends the wrong number of commitments or identity commitments,
+    /// if the combined polynomial has a degree lower than `threshold - 1`,
+    /// if the subgroup check is enabled and the public key is not in the prime-order subgroup,
+    /// if this participant's secret share does not match the combined commitments,
+    /// or [`Error::InvalidVerificationShare`] if no participant was dropped and
+    /// a participant's verification share does not match the combined commitments.
+    ///
+    /// A verifier-only participant runs the same checks except the ones on
+    /// its own shares and only computes the public key and commitments.
     pub fn round4(
         &mut self,
         broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
     ) -> DkgResult<Round4EchoBroadcastData<G>> {
-        if !matches!(self.round, Round::Four) {
-            return Err(Error::RoundError(
-                Round::Four.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
-        }
+        self.check_round(Round::Four)?;
+        self.check_valid_participants(&self.valid_participant_ids)?;
 
         if broadcast_data.is_empty() {
             return Err(Error::RoundError(
@@ -38,73 +55,327 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             ));
         }
 
-        self.public_key = self.components.feldman_verifier_set.verifiers()[0];
+        // Nothing is changed until all the data has been checked
+        // so round 4 can be retried with corrected data
+        let mut valid_participant_ids = self.valid_participant_ids.clone();
+        let mut public_key = self.components.feldman_verifier_set.verifiers()[0];
+        let mut public_commitments = self.components.feldman_verifier_set.verifiers().to_vec();
 
         for (id, bdata) in broadcast_data {
             if self.id == *id {
                 continue;
             }
-            if !self.valid_participant_ids.contains(id) {
+            if !valid_participant_ids.contains(id) {
                 continue;
             }
-            if !self.round1_p2p_data.contains_key(id) {
+            if !self.verifier_only && !self.round1_p2p_data.contains_key(id) {
                 // How would this happen?
                 // Round 2 removed all invalid participants
                 // Round 3 sent echo broadcast to double check valid participants
-                self.valid_participant_ids.remove(id);
+                valid_participant_ids.remove(id);
                 continue;
             }
             if !self.round1_broadcast_data.contains_key(id) {
                 // How would this happen?
                 // Round 2 removed all invalid participants
                 // Round 3 sent echo broadcast to double check valid participants
-                self.valid_participant_ids.remove(id);
+                valid_participant_ids.remove(id);
                 continue;
             }
-            if bdata
-                .commitments
-                .iter()
-                .skip(1)
-                .any(|c| c.is_identity().into())
-            // || !I::check_feldman_verifier(bdata.commitments[0])
+            match self.check_round3_data(*id, bdata) {
+                Ok(true) => {}
+                // An equivocating participant is dropped like one whose share is invalid
+                Ok(false) | Err(Error::Equivocation { .. }) => {
+                    valid_participant_ids.remove(id);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+
+            public_key += bdata.commitments[0];
+            for (pc, c) in public_commitments.iter_mut().zip(bdata.commitments.iter()) {
+                *pc += c;
+            }
+        }
+        self.notify_dropped(
+            self.valid_participant_ids
+                .difference(&valid_participant_ids)
+                .copied(),
+        );
+        self.check_valid_participants(&valid_participant_ids)?;
+        // The top coefficients could cancel out if the dealers coordinate
+        if public_commitments
+            .last()
+            .is_none_or(|c| c.is_identity().into())
+        {
+            return Err(Error::DegradedThreshold);
+        }
+        if let Some(check) = self.subgroup_check {
+            if !check(&public_key) {
+                return Err(Error::InvalidSubgroup);
+            }
+        }
+        // The secret share computed in round 2 without the shares of the
+        // participants dropped above must match the combined commitments
+        let mut secret_share = None;
+        if !self.verifier_only {
+            let mut share =
+                unprotect_scalar::<S, G::Scalar>(&self.secret_share).ok_or_else(|| {
+                    Error::RoundError(Round::Four.into(), "invalid secret unprotected".to_string())
+                })?;
+            for id in self
+                .valid_participant_ids
+                .difference(&valid_participant_ids)
             {
-                self.valid_participant_ids.remove(id);
-                continue;
+                if let Some(p2p) = self.round1_p2p_data.get(id) {
+                    let p2p = unprotect_p2p::<S>(p2p).ok_or_else(|| {
+                        Error::RoundError(
+                            Round::Four.into(),
+                            "invalid secret unprotected".to_string(),
+                        )
+                    })?;
+                    share -= p2p.secret_share.as_field_element::<G::Scalar>()?;
+                }
             }
-            let verifier = Vec::<G>::feldman_set_with_generator_and_verifiers(
-                self.components.feldman_verifier_set.generator(),
-                &bdata.commitments,
-            );
-            let value = &self.round1_p2p_data[id];
-            let mut protected_share = value.deref().lock().map_err(|_e| {
-                Error::RoundError(Round::Four.into(), "unable to lock".to_string())
-            })?;
-            let unprotected = protected_share.unprotect().ok_or_else(|| {
-                Error::RoundError(Round::Four.into(), "invalid secret unprotected".to_string())
-            })?;
-            let round1_p2p_data = unprotected.serde::<Round1P2PData>().unwrap();
-            if verifier
-                .verify_share(&round1_p2p_data.secret_share)
-                .is_err()
+            if self.components.feldman_verifier_set.generator() * share
+                != evaluate_public_polynomial(&public_commitments, self.id)
             {
-                self.valid_participant_ids.remove(id);
-                continue;
+                return Err(Error::ShareSelfCheckFailed);
+            }
+            secret_share = Some(share);
+        }
+        // The verification shares include the shares of any participant dropped
+        // above so they can only be checked if nobody was dropped
+        if valid_participant_ids == self.valid_participant_ids {
+            for id in valid_participant_ids.iter().filter(|id| **id != self.id) {
+                if let Some(bdata) = broadcast_data.get(id) {
+                    if bdata.verification_share
+                        != evaluate_public_polynomial(&public_commitments, *id)
+                    {
+                        return Err(Error::InvalidVerificationShare { from: *id });
+                    }
+                }
             }
+        }
+
+        self.round = Round::Five;
+        self.contributors = broadcast_data.keys().copied().collect();
+        self.valid_participant_ids = valid_participant_ids;
+        if let Some(secret_share) = secret_share {
+            self.secret_share = protect_scalar(&secret_share);
+        }
+        self.public_key = public_key;
+        self.public_commitments = public_commitments;
+
+        Ok(Round4EchoBroadcastData {
+            public_key: self.public_key,
+        })
+    }
 
-            if self.valid_participant_ids.len() < self.threshold {
-                return Err(Error::RoundError(
-                    Round::Four.into(),
-                    "Not enough valid participants to continue".to_string(),
-                ));
+    /// Computes round 4 from the round 3 data of only the participants in `quorum`
+    /// so stragglers do not delay the result.
+    ///
+    /// The valid set is unchanged and the result is the same as [`Participant::round4`]
+    /// with the data of every valid participant. The combined commitments are
+    /// interpolated from the verification shares in the quorum's round 3 data,
+    /// so the public key is their interpolation at zero and any quorum gives
+    /// the same key.
+    ///
+    /// A verification share is reported by its holder, so each one in the quorum
+    /// is checked against the sum of every valid dealer's commitments before it is
+    /// used. The round 3 data of every valid participant is therefore still needed
+    /// and is checked like in [`Participant::round4`], except that a dealer whose
+    /// data does not verify is an error instead of being dropped.
+    ///
+    /// Throws an error if `quorum` has fewer than `threshold` ids or contains an id
+    /// that is not a valid participant, if a valid participant has no data in
+    /// `broadcast_data` or its data does not verify,
+    /// [`Error::InvalidVerificationShare`] if the verification share of a quorum
+    /// member does not match the commitments, or any error of [`Participant::round4`].
+    /// Nothing is changed if an error is returned.
+    pub fn round4_with_quorum(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
+        quorum: &BTreeSet<usize>,
+    ) -> DkgResult<Round4EchoBroadcastData<G>> {
+        self.check_round(Round::Four)?;
+        self.check_valid_participants(&self.valid_participant_ids)?;
+        if quorum.len() < self.threshold {
+            return Err(Error::InsufficientParticipants {
+                have: quorum.len(),
+                need: self.threshold,
+            });
+        }
+        if let Some(id) = quorum.difference(&self.valid_participant_ids).next() {
+            return Err(Error::InvalidInput(format!(
+                "secret_participant {} is not a valid participant",
+                id
+            )));
+        }
+
+        let generator = self.components.feldman_verifier_set.generator();
+        let secret_share = if self.verifier_only {
+            None
+        } else {
+            Some(
+                unprotect_scalar::<S, G::Scalar>(&self.secret_share).ok_or_else(|| {
+                    Error::RoundError(Round::Four.into(), "invalid secret unprotected".to_string())
+                })?,
+            )
+        };
+        let mut dealer_commitments = self.components.feldman_verifier_set.verifiers().to_vec();
+        for id in self
+            .valid_participant_ids
+            .iter()
+            .filter(|id| **id != self.id)
+        {
+            let bdata = broadcast_data.get(id).ok_or_else(|| {
+                Error::InvalidInput(format!("secret_participant {} has no round 3 data", id))
+            })?;
+            if !self.check_round3_data(*id, bdata)? {
+                return Err(Error::InvalidCommitment { from: *id });
+            }
+            for (dc, c) in dealer_commitments.iter_mut().zip(bdata.commitments.iter()) {
+                *dc += c;
+            }
+        }
+        let mut verification_shares = BTreeMap::new();
+        for id in quorum {
+            if *id == self.id {
+                // Use this participant's own share rather than the copy it received
+                if let Some(secret_share) = secret_share {
+                    verification_shares.insert(*id, generator * secret_share);
+                }
+                continue;
+            }
+            let verification_share = broadcast_data[id].verification_share;
+            if verification_share != evaluate_public_polynomial(&dealer_commitments, *id) {
+                return Err(Error::InvalidVerificationShare { from: *id });
             }
+            verification_shares.insert(*id, verification_share);
+        }
 
-            self.public_key += bdata.commitments[0];
+        let mut points = verification_shares.into_iter();
+        let public_commitments = interpolate_public_polynomial(
+            &points
+                .by_ref()
+                .take(self.threshold)
+                .collect::<BTreeMap<_, _>>(),
+        )?;
+        for (id, verification_share) in points {
+            if evaluate_public_polynomial(&public_commitments, id) != verification_share {
+                return Err(Error::InvalidCommitment { from: id });
+            }
+        }
+        // The top coefficients could cancel out if the dealers coordinate
+        if public_commitments
+            .last()
+            .is_none_or(|c| c.is_identity().into())
+        {
+            return Err(Error::DegradedThreshold);
+        }
+        let public_key = public_commitments[0];
+        if let Some(check) = self.subgroup_check {
+            if !check(&public_key) {
+                return Err(Error::InvalidSubgroup);
+            }
+        }
+        if let Some(secret_share) = secret_share {
+            if generator * secret_share != evaluate_public_polynomial(&public_commitments, self.id)
+            {
+                return Err(Error::ShareSelfCheckFailed);
+            }
         }
 
         self.round = Round::Five;
+        self.contributors = self.valid_participant_ids.clone();
+        self.public_key = public_key;
+        self.public_commitments = public_commitments;
 
         Ok(Round4EchoBroadcastData {
             public_key: self.public_key,
         })
     }
+
+    /// Computes round 4 like [`Participant::round4`] and also returns the
+    /// combined commitments and this participant's verification share so a
+    /// signing coordinator can build the key package from a single message.
+    pub fn round4_output(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
+    ) -> DkgResult<Round4Output<G>> {
+        let broadcast = self.round4(broadcast_data)?;
+        Ok(Round4Output {
+            broadcast,
+            id: self.id,
+            public_commitments: self.public_commitments.clone(),
+            verification_share: self.verification_share(self.id),
+        })
+    }
+
+    /// Computes round 4 like [`Participant::round4`] and hands the final
+    /// secret share to `sink` instead of keeping it in this participant.
+    ///
+    /// Afterwards [`Participant::get_secret_share`] returns [`None`] and
+    /// methods that need the share throw [`Error::ShareInSink`].
+    /// If the sink fails the share is kept in this participant.
+    ///
+    /// Throws the errors of [`Participant::round4`] or the error from `sink`.
+    pub fn round4_with_sink(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
+        sink: &mut impl ShareSink<G::Scalar>,
+    ) -> DkgResult<Round4EchoBroadcastData<G>> {
+        if self.verifier_only {
+            return Err(Error::VerifierOnly);
+        }
+        let echo_data = self.round4(broadcast_data)?;
+        sink.store(self.try_get_secret_share()?)?;
+        self.secret_share.wipe();
+        self.share_in_sink = true;
+        Ok(echo_data)
+    }
+
+    /// Check the round 3 data from participant `id` against its round 1 data.
+    ///
+    /// Returns `false` if the commitments do not verify the share that
+    /// `id` sent to this participant.
+    fn check_round3_data(&self, id: usize, bdata: &Round3BroadcastData<G>) -> DkgResult<bool> {
+        let round1_bdata = self.round1_broadcast_data.get(&id).ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "secret_participant {} has no round 1 broadcast data",
+                id
+            ))
+        })?;
+        if bdata.pedersen_commitments != round1_bdata.pedersen_commitments {
+            return Err(Error::Equivocation { id });
+        }
+        // The constant term is not checked since refresh participants
+        // can take part alongside secret participants
+        if bdata.commitments.len() != self.threshold
+            || bdata
+                .commitments
+                .iter()
+                .skip(1)
+                .any(|c| c.is_identity().into())
+        {
+            return Err(Error::InvalidCommitment { from: id });
+        }
+        // A verifier-only participant holds no shares to check
+        if self.verifier_only {
+            return Ok(true);
+        }
+        let round1_p2p_data = self
+            .round1_p2p_data
+            .get(&id)
+            .and_then(unprotect_p2p::<S>)
+            .ok_or_else(|| {
+                Error::RoundError(Round::Four.into(), "invalid secret unprotected".to_string())
+            })?;
+        let verifier = Vec::<G>::feldman_set_with_generator_and_verifiers(
+            self.components.feldman_verifier_set.generator(),
+            &bdata.commitments,
+        );
+        Ok(verifier.verify_share(&round1_p2p_data.secret_share).is_ok())
+    }
 }
@@ -1,6 +1,11 @@
 use super::*;
 
-impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
     /// Computes round 5 for this participant.
     ///
     /// Checks if all participants computed the same public key.
@@ -10,12 +15,7 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
         &self,
         broadcast_data: &BTreeMap<usize, Round4EchoBroadcastData<G>>,
     ) -> DkgResult<()> {
-        if !matches!(self.round, Round::Five) {
-            return Err(Error::RoundError(
-                Round::Five.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
-        }
+        self.check_round(Round::Five)?;
         if broadcast_data.is_empty() {
             return Err(Error::RoundError(
                 Round::Five.into(),
@@ -38,7 +38,7 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             if !self.valid_participant_ids.contains(id) {
                 continue;
             }
-            if !self.round1_p2p_data.contains_key(id) {
+            if !self.verifier_only && !self.round1_p2p_data.contains_key(id) {
                 // How would this happen?
                 // Round 2 removed all invalid participants
                 // Round 3 sent echo broadcast to double check valid participants
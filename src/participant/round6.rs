@@ -0,0 +1,82 @@
+use super::*;
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    /// Computes the optional round 6 for this participant.
+    ///
+    /// Proves this participant still holds the secret share behind its
+    /// verification share by signing the group public key with it.
+    /// The proof should be broadcast to all valid participants and
+    /// checked with [`Participant::verify_pop`].
+    ///
+    /// Throws an error if this participant has not completed the protocol
+    /// or [`Error::RngFailure`] if the OS entropy source is unavailable.
+    pub fn round6_pop(&self) -> DkgResult<PopData<G>> {
+        let secret_share = self.try_get_secret_share()?;
+        Ok(PopData::sign(
+            self.id,
+            &secret_share,
+            self.public_key,
+            self.components.feldman_verifier_set.generator(),
+            entropy_rng()?,
+        ))
+    }
+
+    /// Check the proof of possession from another participant against
+    /// its verification share.
+    ///
+    /// Throws an error if this participant has not completed the protocol,
+    /// [`Error::InvalidInput`] if the sender is not a valid participant,
+    /// or [`Error::BadSignature`] if the proof is invalid.
+    pub fn verify_pop(&self, pop: &PopData<G>) -> DkgResult<()> {
+        let public_key = self.try_get_public_key()?;
+        if !self.valid_participant_ids.contains(&pop.id) {
+            return Err(Error::InvalidInput(format!(
+                "secret_participant {} is not a valid participant",
+                pop.id
+            )));
+        }
+        if pop.verify(
+            self.verification_share(pop.id),
+            public_key,
+            self.components.feldman_verifier_set.generator(),
+        ) {
+            Ok(())
+        } else {
+            Err(Error::BadSignature { from: pop.id })
+        }
+    }
+
+    /// Create a signed [`CeremonyManifest`] describing the outcome of the DKG.
+    ///
+    /// The manifest lists the parameters and their fingerprint, the valid and
+    /// dropped participant ids, the public key and commitments, and a hash of
+    /// all of them signed with this participant's secret share. All honest
+    /// participants produce the same manifest hash. The round broadcasts are
+    /// not part of the manifest.
+    ///
+    /// Throws an error if this participant has not completed the protocol
+    /// or [`Error::RngFailure`] if the OS entropy source is unavailable.
+    pub fn manifest(&self) -> DkgResult<CeremonyManifest<G>> {
+        self.manifest_with::<Sha256Hasher>()
+    }
+
+    /// Create a [`CeremonyManifest`] using the hasher `H` for the parameters
+    /// fingerprint and manifest hash, see [`Participant::manifest`].
+    /// It must be checked with [`CeremonyManifest::verify_manifest_with`].
+    pub fn manifest_with<H: DkgHasher>(&self) -> DkgResult<CeremonyManifest<G>> {
+        let secret_share = self.try_get_secret_share()?;
+        Ok(CeremonyManifest::new::<H>(
+            self.get_parameters(),
+            self.valid_participant_ids.clone(),
+            self.public_commitments.clone(),
+            self.id,
+            &secret_share,
+            entropy_rng()?,
+        ))
+    }
+}
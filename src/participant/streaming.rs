@@ -0,0 +1,27 @@
+use super::*;
+use serde::de::DeserializeOwned;
+use std::io::{Read, Write};
+
+impl<I, G, S> Participant<I, G, S>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+    Self: Serialize + DeserializeOwned,
+{
+    /// Serialize this participant directly into `w`.
+    ///
+    /// The round data is written as it is encoded so no buffer holding the
+    /// whole participant is created. This keeps the peak memory bounded
+    /// when persisting the state of very large committees between rounds.
+    /// The output is the same as serializing with `serde_bare`.
+    pub fn serialize_to_writer<W: Write>(&self, w: W) -> DkgResult<()> {
+        serde_bare::to_writer(w, self).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Deserialize a participant written with [`Participant::serialize_to_writer`]
+    /// by reading incrementally from `r`.
+    pub fn deserialize_from_reader<R: Read>(r: R) -> DkgResult<Self> {
+        serde_bare::from_reader(r).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+}
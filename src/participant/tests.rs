@@ -0,0 +1,562 @@
+use super::*;
+use crate::test_util::{complete_dkg, new_participants, round2_inputs, run_dkg, run_round1};
+
+type G = k256::ProjectivePoint;
+
+fn run_to_round4(
+    participants: &mut [SecretParticipant<G>],
+) -> BTreeMap<usize, Round3BroadcastData<G>> {
+    let (r1bdata, r1p2pdata) = run_round1(participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    r3bdata
+}
+
+#[test]
+fn round4_checks_valid_participants_first() {
+    let mut participants = new_participants::<G>(3, 4);
+    let r3bdata = run_to_round4(&mut participants);
+
+    // Already below the threshold before any data is processed
+    participants[1].valid_participant_ids.remove(&3);
+    participants[1].valid_participant_ids.remove(&4);
+    assert!(matches!(
+        participants[1].round4(&r3bdata),
+        Err(Error::InsufficientParticipants { have: 2, need: 3 })
+    ));
+}
+
+#[test]
+fn debug_redacts_components() {
+    let mut participants = new_participants::<G>(2, 3);
+    run_round1(&mut participants);
+    let output = format!("{:?}", participants[0]);
+    assert!(!output.contains(&format!("{:?}", participants[0].components.blinder)));
+}
+
+#[test]
+fn blinder_shares_cleared_after_round2() {
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    assert_eq!(participants[0].components.blinder_shares.len(), 3);
+
+    let (bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    participants[0].round2(bdata, p2p).unwrap();
+    assert!(participants[0].components.blinder_shares.is_empty());
+    assert_eq!(participants[0].components.blinder, k256::Scalar::ZERO);
+
+    let mut participants = new_participants::<G>(2, 3);
+    complete_dkg(&mut participants);
+    for p in &participants {
+        assert!(p.completed());
+        assert!(p.components.blinder_shares.is_empty());
+    }
+}
+
+#[test]
+fn share_self_check() {
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+
+    // Corrupt the stored share from participant 2 after round 2 accepted it
+    let mut tampered = r1p2pdata[&2][&1].clone();
+    *tampered.secret_share.last_mut().unwrap() ^= 1;
+    participants[0]
+        .round1_p2p_data
+        .insert(2, protect_p2p(&tampered));
+    assert!(matches!(
+        participants[0].round4(&r3bdata),
+        Err(Error::ShareSelfCheckFailed)
+    ));
+    assert_eq!(participants[0].get_round(), Round::Four);
+
+    for p in participants.iter_mut().skip(1) {
+        assert!(p.round4(&r3bdata).is_ok());
+    }
+}
+
+#[test]
+fn subgroup_check_failure() {
+    let mut participants = new_participants::<G>(2, 3);
+    let r3bdata = run_to_round4(&mut participants);
+    participants[0].enable_subgroup_check();
+    participants[0].subgroup_check = Some(|_| false);
+    assert!(matches!(
+        participants[0].round4(&r3bdata),
+        Err(Error::InvalidSubgroup)
+    ));
+}
+
+#[test]
+fn abort_wipes_secrets() {
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let (bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    participants[0].round2(bdata, p2p).unwrap();
+
+    // Clones share the protected storage so they are wiped too
+    let copy = participants[0].clone();
+    assert!(unprotect_scalar::<ProtectedStore, <G as Group>::Scalar>(&copy.secret_share).is_some());
+    assert!(copy
+        .round1_p2p_data
+        .values()
+        .all(|s| s.unprotect().is_some()));
+
+    let aborted = participants.remove(0).abort();
+    assert_eq!(aborted.get_id(), 1);
+    assert_eq!(aborted.get_round(), Round::Three);
+    assert!(unprotect_scalar::<ProtectedStore, <G as Group>::Scalar>(&copy.secret_share).is_none());
+    assert!(copy
+        .round1_p2p_data
+        .values()
+        .all(|s| unprotect_p2p(s).is_none()));
+
+    let mut store = InMemoryStore::protect(&[1, 2, 3]);
+    store.wipe();
+    assert_eq!(store.unprotect().unwrap().len(), 0);
+}
+
+#[test]
+fn equivocation_k256() {
+    equivocation::<k256::ProjectivePoint>()
+}
+
+#[test]
+fn equivocation_bls12_381() {
+    equivocation::<bls12_381_plus::G1Projective>()
+}
+
+fn equivocation<G: Group + GroupEncoding + Default>() {
+    const BAD_ID: usize = 3;
+
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+
+    // The bad actor claims different round 1 commitments than it sent
+    r3bdata.get_mut(&BAD_ID).unwrap().pedersen_commitments[0] = G::generator();
+
+    // The honest participants drop the bad actor and continue without it
+    participants.retain(|p| p.get_id() != BAD_ID);
+    let mut r4bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        assert_eq!(p.get_valid_participant_ids(), &maplit::btreeset! {1, 2});
+    }
+    let public_key = r3bdata[&1].commitments[0] + r3bdata[&2].commitments[0];
+    for p in participants.iter() {
+        p.round5(&r4bdata).unwrap();
+        assert_eq!(p.get_public_key(), Some(public_key));
+    }
+}
+
+#[test]
+fn insufficient_participants_round4() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(3, 4);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+
+    // Participant 3 and 4's round 3 commitments don't verify their shares
+    r3bdata.get_mut(&3).unwrap().commitments[1] = k256::ProjectivePoint::GENERATOR;
+    r3bdata.get_mut(&4).unwrap().commitments[1] = k256::ProjectivePoint::GENERATOR;
+    assert!(matches!(
+        participants[0].round4(&r3bdata),
+        Err(Error::InsufficientParticipants { have: 2, need: 3 })
+    ));
+}
+
+#[test]
+fn invalid_round3_commitments() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+
+    let mut identity = r3bdata.clone();
+    identity.get_mut(&3).unwrap().commitments[1] = G::IDENTITY;
+    assert!(matches!(
+        participants[0].clone().round4(&identity),
+        Err(Error::InvalidCommitment { from: 3 })
+    ));
+
+    let mut short = r3bdata.clone();
+    short.get_mut(&2).unwrap().commitments.pop();
+    assert!(matches!(
+        participants[0].clone().round4(&short),
+        Err(Error::InvalidCommitment { from: 2 })
+    ));
+
+    assert!(participants[0].round4(&r3bdata).is_ok());
+}
+
+#[test]
+fn round1_output() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    let output = participants[0].round1_output().unwrap();
+    assert!(output.p2p_for(1).is_none());
+    assert!(output.p2p_for(2).is_some());
+    assert!(output.p2p_for(3).is_some());
+    assert_eq!(output.broadcast.pedersen_commitments.len(), 2);
+    assert!(matches!(
+        participants[0].round1_output(),
+        Err(Error::RoundOrder { .. })
+    ));
+}
+
+#[test]
+fn all_required_aborts() {
+    const BAD_ID: usize = 3;
+    let mut participants = new_participants::<k256::ProjectivePoint>(3, 3);
+    let (mut r1bdata, r1p2pdata) = run_round1(&mut participants);
+    r1bdata.get_mut(&BAD_ID).unwrap().pedersen_commitments[0] = k256::ProjectivePoint::GENERATOR;
+
+    for p in participants.iter_mut().filter(|p| p.get_id() != BAD_ID) {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        assert!(matches!(
+            p.round2(bdata, p2p),
+            Err(Error::Abort(AbortReason::TooFewValidParticipants))
+        ));
+        assert!(p.get_secret_share().is_none());
+    }
+}
+
+#[test]
+fn rounds_can_be_retried() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(3, 4);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    // Corrupted data from participants 3 and 4 puts participant 1 below the threshold
+    let (mut bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    bdata.get_mut(&3).unwrap().pedersen_commitments[1] = G::GENERATOR;
+    bdata.get_mut(&4).unwrap().pedersen_commitments[1] = G::GENERATOR;
+    assert!(participants[0].round2(bdata, p2p).is_err());
+    assert_eq!(participants[0].get_round(), Round::Two);
+    assert!(participants[0].get_valid_participant_ids().is_empty());
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+
+    let mut bad = r3bdata.clone();
+    bad.get_mut(&3).unwrap().commitments[1] = G::GENERATOR;
+    bad.get_mut(&4).unwrap().commitments[1] = G::GENERATOR;
+    assert!(participants[0].round4(&bad).is_err());
+    assert_eq!(participants[0].get_round(), Round::Four);
+    assert_eq!(participants[0].valid_participant_count(), 4);
+    assert_eq!(participants[0].public_key_so_far(), G::IDENTITY);
+
+    let public_key = participants[0].round4(&r3bdata).unwrap().public_key;
+    assert_eq!(participants[0].get_public_key(), Some(public_key));
+}
+
+#[test]
+fn drop_observer() {
+    use std::sync::{Arc, Mutex};
+
+    const BAD_ID: usize = 4;
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 4);
+    let dropped = Arc::new(Mutex::new(Vec::new()));
+    let observed = dropped.clone();
+    participants[0].set_on_drop(move |id| observed.lock().unwrap().push(id));
+    participants[1].set_on_drop(|_| panic!("observer failure"));
+
+    let (mut r1bdata, r1p2pdata) = run_round1(&mut participants);
+    r1bdata.get_mut(&BAD_ID).unwrap().pedersen_commitments[0] = G::GENERATOR;
+
+    for p in participants.iter_mut().take(2) {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        assert!(p.round2(bdata, p2p).is_ok());
+        assert!(!p.get_valid_participant_ids().contains(&BAD_ID));
+    }
+    assert_eq!(*dropped.lock().unwrap(), vec![BAD_ID]);
+}
+
+#[cfg(feature = "signed-echo")]
+#[test]
+fn signed_echo() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    for (id, echo) in &r2bdata {
+        assert!(echo.signature().is_some());
+        assert!(echo.verify_signature(*id, &r1bdata[id]));
+        assert!(!echo.verify_signature(*id % 3 + 1, &r1bdata[id]));
+        assert!(!echo.verify_signature(*id, &r1bdata[&(*id % 3 + 1)]));
+    }
+
+    let json = serde_json::to_string(&r2bdata[&2]).unwrap();
+    let echo = serde_json::from_str::<Round2EchoBroadcastData>(&json).unwrap();
+    assert!(echo.verify_signature(2, &r1bdata[&2]));
+
+    // A signature over a different set is rejected in round 3
+    let mut forged = r2bdata.clone();
+    forged.get_mut(&2).unwrap().signature = r2bdata[&3].signature.clone();
+    assert!(participants[0].clone().round3(&forged).is_err());
+
+    // Unsigned echoes are rejected
+    let mut unsigned = r2bdata.clone();
+    unsigned.get_mut(&2).unwrap().signature = None;
+    assert!(participants[0].clone().round3(&unsigned).is_err());
+    assert!(participants[0].round3(&r2bdata).is_ok());
+}
+
+#[test]
+fn multiple_bad_dealers() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(3, 5);
+    let (r1bdata, mut r1p2pdata) = run_round1(&mut participants);
+    for dealer in [3, 5] {
+        let mut share = r1p2pdata[&dealer][&1].clone();
+        *share.secret_share.last_mut().unwrap() ^= 1;
+        r1p2pdata.get_mut(&dealer).unwrap().insert(1, share);
+    }
+    let (bdata, mut p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+
+    // Enough remain so both are dropped
+    assert!(participants[0]
+        .clone()
+        .round2(bdata.clone(), p2p.clone())
+        .is_ok());
+
+    let mut share = p2p[&4].clone();
+    *share.blind_share.last_mut().unwrap() ^= 1;
+    p2p.insert(4, share);
+    let res = participants[0].round2(bdata, p2p);
+    assert!(matches!(
+        res,
+        Err(Error::MultipleBadDealers(ids)) if ids == maplit::btreeset! {3, 4, 5}
+    ));
+}
+
+#[test]
+fn processed_peers() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 4);
+    let (r1bdata, mut r1p2pdata) = run_round1(&mut participants);
+    // Participant 4 sends an invalid share to participant 1
+    let p2p = r1p2pdata.get_mut(&4).unwrap().get_mut(&1).unwrap();
+    let last = p2p.secret_share.len() - 1;
+    p2p.secret_share[last] ^= 1;
+
+    let (bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    participants[0].round2(bdata, p2p).unwrap();
+    assert_eq!(
+        participants[0].processed_peers(Round::Two),
+        maplit::btreeset! {2, 3, 4}
+    );
+    assert!(participants[0].processed_peers(Round::Three).is_empty());
+    assert!(participants[0].processed_peers(Round::One).is_empty());
+
+    let participants = run_dkg::<k256::ProjectivePoint>(2, 3);
+    for round in [Round::Three, Round::Four] {
+        assert_eq!(
+            participants[1].processed_peers(round),
+            maplit::btreeset! {1, 3}
+        );
+    }
+    assert!(participants[1].processed_peers(Round::Five).is_empty());
+}
+
+#[test]
+fn round4_with_quorum() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 4);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    let mut full = participants.clone();
+    for p in full.iter_mut() {
+        p.round4(&r3bdata).unwrap();
+    }
+    let expected = full[0].get_public_key().unwrap();
+    let quorum = maplit::btreeset! {1, 2, 3};
+
+    assert!(participants[0]
+        .round4_with_quorum(&r3bdata, &maplit::btreeset! {1})
+        .is_err());
+    // The verification shares are checked against every valid dealer
+    let mut missing = r3bdata.clone();
+    missing.remove(&4);
+    assert!(matches!(
+        participants[0].round4_with_quorum(&missing, &quorum),
+        Err(Error::InvalidInput(_))
+    ));
+    let mut forged = r3bdata.clone();
+    forged.get_mut(&3).unwrap().verification_share = G::GENERATOR;
+    assert!(matches!(
+        participants[0].round4_with_quorum(&forged, &quorum),
+        Err(Error::InvalidVerificationShare { from: 3 })
+    ));
+    assert!(matches!(
+        participants[0].clone().round4(&forged),
+        Err(Error::InvalidVerificationShare { from: 3 })
+    ));
+    assert_eq!(participants[0].get_round(), Round::Four);
+
+    let mut r4bdata = BTreeMap::new();
+    for p in participants[..3].iter_mut() {
+        r4bdata.insert(p.get_id(), p.round4_with_quorum(&r3bdata, &quorum).unwrap());
+    }
+    // Any quorum gives the same key
+    r4bdata.insert(
+        4,
+        participants[3]
+            .round4_with_quorum(&r3bdata, &maplit::btreeset! {2, 4})
+            .unwrap(),
+    );
+    for (p, q) in participants.iter().zip(full.iter()) {
+        p.round5(&r4bdata).unwrap();
+        assert_eq!(p.get_public_key().unwrap(), expected);
+        assert_eq!(p.get_public_commitments(), q.get_public_commitments());
+        assert_eq!(p.get_secret_share(), q.get_secret_share());
+        assert_eq!(p.get_valid_participant_ids().len(), 4);
+    }
+}
+
+#[test]
+fn blinder_commitments() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    assert!(participants[0].blinder_commitments().is_err());
+    let parameters = participants[0].get_parameters();
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    assert!(participants[0].blinder_commitments().is_err());
+    let r3bdata = participants[0].round3(&r2bdata).unwrap();
+
+    let blinder_commitments = participants[0].blinder_commitments().unwrap();
+    assert_eq!(
+        Some(blinder_commitments.clone()),
+        r3bdata.blinder_commitments()
+    );
+    for ((c, b), p) in r3bdata
+        .commitments
+        .iter()
+        .zip(blinder_commitments.iter())
+        .zip(r1bdata[&1].pedersen_commitments.iter())
+    {
+        assert_eq!(*c + b, *p);
+    }
+    // Each blind share is an evaluation of the blinder polynomial
+    let blind_share = r1p2pdata[&1][&2]
+        .blind_share
+        .as_field_element::<k256::Scalar>()
+        .unwrap();
+    assert_eq!(
+        parameters.blinder_generator * blind_share,
+        evaluate_public_polynomial(&blinder_commitments, 2)
+    );
+}
+
+#[test]
+fn round2_rejects_short_commitments() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(3, 4);
+    let (mut bdata, p2p) = run_round1(&mut participants);
+    bdata.get_mut(&3).unwrap().pedersen_commitments.pop();
+
+    let (bmap, p2pmap) = round2_inputs(1, &bdata, &p2p);
+    assert!(matches!(
+        participants[0].round2(bmap, p2pmap),
+        Err(Error::InvalidCommitment { from: 3 })
+    ));
+    assert_eq!(participants[0].get_round(), Round::Two);
+}
+
+#[test]
+fn verify_incoming_per_dealer() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (bdata, p2p) = run_round1(&mut participants);
+
+    assert!(participants[0]
+        .verify_incoming(2, &bdata[&2], &p2p[&2][&1])
+        .is_ok());
+    assert!(participants[0]
+        .verify_incoming(1, &bdata[&1], &p2p[&1][&2])
+        .is_err());
+    // A share meant for participant 3 does not verify for participant 1
+    assert!(matches!(
+        participants[0].verify_incoming(2, &bdata[&2], &p2p[&2][&3]),
+        Err(Error::RoundError(2, _))
+    ));
+    let mut tampered = bdata[&3].clone();
+    tampered.pedersen_commitments[1] = G::IDENTITY;
+    assert!(matches!(
+        participants[0].verify_incoming(3, &tampered, &p2p[&3][&1]),
+        Err(Error::InvalidCommitment { from: 3 })
+    ));
+
+    // Round 2 accepts the same data
+    let (bmap, p2pmap) = round2_inputs(1, &bdata, &p2p);
+    participants[0].round2(bmap, p2pmap).unwrap();
+    assert!(participants[0]
+        .verify_incoming(2, &bdata[&2], &p2p[&2][&1])
+        .is_err());
+}
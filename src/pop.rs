@@ -0,0 +1,55 @@
+use crate::SchnorrSignature;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+const POP_DOMAIN: &[u8] = b"gennaro-dkg proof of possession";
+
+/// A proof that a participant holds the secret share behind its verification share.
+///
+/// See [`Participant::round6_pop`](crate::Participant::round6_pop).
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct PopData<G: Group + GroupEncoding + Default> {
+    /// The id of the participant that created the proof
+    pub id: usize,
+    /// A signature over the group public key under the participant's secret share
+    #[serde(bound(serialize = "SchnorrSignature<G>: Serialize"))]
+    #[serde(bound(deserialize = "SchnorrSignature<G>: Deserialize<'de>"))]
+    pub signature: SchnorrSignature<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> PopData<G> {
+    /// Prove possession of `secret_share` for participant `id`
+    pub(crate) fn sign(
+        id: usize,
+        secret_share: &G::Scalar,
+        public_key: G,
+        generator: G,
+        rng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let signature = SchnorrSignature::sign_with_generator(
+            generator,
+            secret_share,
+            &Self::message(id, public_key),
+            rng,
+        );
+        Self { id, signature }
+    }
+
+    /// Check the proof against the participant's `verification_share`,
+    /// the group `public_key` and the feldman `generator` used in the DKG
+    pub fn verify(&self, verification_share: G, public_key: G, generator: G) -> bool {
+        self.signature.verify_with_generator(
+            generator,
+            verification_share,
+            &Self::message(self.id, public_key),
+        )
+    }
+
+    fn message(id: usize, public_key: G) -> Vec<u8> {
+        let mut message = POP_DOMAIN.to_vec();
+        message.extend_from_slice(&(id as u64).to_be_bytes());
+        message.extend_from_slice(public_key.to_bytes().as_ref());
+        message
+    }
+}
@@ -0,0 +1,64 @@
+//! Type aliases for the curves this crate is tested against.
+//!
+//! Each curve is behind a feature so only the curves that are used
+//! are compiled:
+//!
+//! | Feature     | Curve      | Group                              |
+//! |-------------|------------|------------------------------------|
+//! | `k256`      | secp256k1  | `k256::ProjectivePoint`            |
+//! | `p256`      | NIST P-256 | `p256::ProjectivePoint`            |
+//! | `bls12_381` | BLS12-381  | `bls12_381_plus::G1Projective`     |
+//!
+//! The curve crates are re-exported so the versions always match.
+
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+pub use bls12_381_plus;
+#[cfg(feature = "k256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "k256")))]
+pub use k256;
+#[cfg(feature = "p256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "p256")))]
+pub use p256;
+
+#[cfg(any(feature = "k256", feature = "p256", feature = "bls12_381"))]
+use crate::{Parameters, RefreshParticipant, SecretParticipant};
+
+/// Secret participant on secp256k1
+#[cfg(feature = "k256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "k256")))]
+pub type Secp256k1Participant = SecretParticipant<k256::ProjectivePoint>;
+/// Refresh participant on secp256k1
+#[cfg(feature = "k256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "k256")))]
+pub type Secp256k1RefreshParticipant = RefreshParticipant<k256::ProjectivePoint>;
+/// Parameters on secp256k1
+#[cfg(feature = "k256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "k256")))]
+pub type Secp256k1Parameters = Parameters<k256::ProjectivePoint>;
+
+/// Secret participant on NIST P-256
+#[cfg(feature = "p256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "p256")))]
+pub type P256Participant = SecretParticipant<p256::ProjectivePoint>;
+/// Refresh participant on NIST P-256
+#[cfg(feature = "p256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "p256")))]
+pub type P256RefreshParticipant = RefreshParticipant<p256::ProjectivePoint>;
+/// Parameters on NIST P-256
+#[cfg(feature = "p256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "p256")))]
+pub type P256Parameters = Parameters<p256::ProjectivePoint>;
+
+/// Secret participant on BLS12-381 G1
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+pub type Bls12381G1Participant = SecretParticipant<bls12_381_plus::G1Projective>;
+/// Refresh participant on BLS12-381 G1
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+pub type Bls12381G1RefreshParticipant = RefreshParticipant<bls12_381_plus::G1Projective>;
+/// Parameters on BLS12-381 G1
+#[cfg(feature = "bls12_381")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bls12_381")))]
+pub type Bls12381G1Parameters = Parameters<bls12_381_plus::G1Projective>;
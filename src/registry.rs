@@ -0,0 +1,121 @@
+use crate::{DkgResult, Error, Parameters, Round1BroadcastData, Round1P2PData};
+use std::collections::{BTreeMap, BTreeSet};
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+/// The round 2 inputs of one participant: the round 1 broadcast data and
+/// peer-to-peer data keyed by sender, see [`ParticipantRegistry::round2_inputs`]
+pub type Round2Inputs<G> = (
+    BTreeMap<usize, Round1BroadcastData<G>>,
+    BTreeMap<usize, Round1P2PData>,
+);
+
+/// Collects round 1 broadcast data while participants come online
+/// and then freezes the participant set before round 2.
+///
+/// Participants that register after the set is frozen are excluded.
+/// Round 2 inputs taken from a frozen registry only contain members
+/// so a late joiner's messages never reach the other participants.
+#[derive(Clone, Debug)]
+pub struct ParticipantRegistry<G: Group + GroupEncoding + Default> {
+    parameters: Parameters<G>,
+    broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+    frozen: bool,
+}
+
+impl<G: Group + GroupEncoding + Default> ParticipantRegistry<G> {
+    /// Open a registration window for a DKG using `parameters`
+    pub fn new(parameters: Parameters<G>) -> Self {
+        Self {
+            parameters,
+            broadcast_data: BTreeMap::new(),
+            frozen: false,
+        }
+    }
+
+    /// Register participant `id` with its round 1 broadcast data.
+    ///
+    /// Throws an error if the set is frozen, `id` is not between 1 and `limit`,
+    /// or `id` is already registered.
+    pub fn register(&mut self, id: usize, data: Round1BroadcastData<G>) -> DkgResult<()> {
+        if self.frozen {
+            return Err(Error::InvalidInput(format!(
+                "the registration window is closed, participant {} is excluded",
+                id
+            )));
+        }
+        if id == 0 || id > self.parameters.limit {
+            return Err(Error::InvalidInput(format!(
+                "participant id {} is not between 1 and {}",
+                id, self.parameters.limit
+            )));
+        }
+        if self.broadcast_data.contains_key(&id) {
+            return Err(Error::InvalidInput(format!(
+                "participant {} is already registered",
+                id
+            )));
+        }
+        self.broadcast_data.insert(id, data);
+        Ok(())
+    }
+
+    /// Close the registration window and return the registered ids.
+    ///
+    /// Throws an error if fewer than `threshold` participants registered.
+    /// The registry stays open in that case so more participants can join.
+    pub fn freeze(&mut self) -> DkgResult<BTreeSet<usize>> {
+        if self.broadcast_data.len() < self.parameters.threshold {
+            return Err(Error::InsufficientParticipants {
+                have: self.broadcast_data.len(),
+                need: self.parameters.threshold,
+            });
+        }
+        self.frozen = true;
+        Ok(self.members())
+    }
+
+    /// True once [`ParticipantRegistry::freeze`] has succeeded
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// The ids registered so far
+    pub fn members(&self) -> BTreeSet<usize> {
+        self.broadcast_data.keys().copied().collect()
+    }
+
+    /// True if `id` is registered
+    pub fn is_member(&self, id: usize) -> bool {
+        self.broadcast_data.contains_key(&id)
+    }
+
+    /// The round 2 inputs for participant `id`: the broadcast data of every
+    /// other member and the entries of `p2p_data` that were sent by members.
+    ///
+    /// Throws an error if the set is not frozen or `id` is not a member.
+    pub fn round2_inputs(
+        &self,
+        id: usize,
+        mut p2p_data: BTreeMap<usize, Round1P2PData>,
+    ) -> DkgResult<Round2Inputs<G>> {
+        if !self.frozen {
+            return Err(Error::InvalidInput(
+                "the registration window is still open".to_string(),
+            ));
+        }
+        if !self.is_member(id) {
+            return Err(Error::InvalidInput(format!(
+                "participant {} is not registered",
+                id
+            )));
+        }
+        p2p_data.retain(|from, _| *from != id && self.is_member(*from));
+        let broadcast_data = self
+            .broadcast_data
+            .iter()
+            .filter(|(from, _)| **from != id)
+            .map(|(from, data)| (*from, data.clone()))
+            .collect();
+        Ok((broadcast_data, p2p_data))
+    }
+}
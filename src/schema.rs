@@ -0,0 +1,25 @@
+/// Describes a single field in the JSON wire format of a round message.
+///
+/// The JSON field names are fixed with `#[serde(rename)]` and will not
+/// change when the Rust field names do, so implementations in other
+/// languages can rely on them.
+///
+/// Points are encoded as unpadded URL-safe base64 of the curve's
+/// compressed `GroupEncoding` bytes. Shares are an array of byte values
+/// where the first byte is the share identifier and the remaining bytes
+/// are the scalar's `PrimeField::to_repr` encoding.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldSchema {
+    /// The JSON field name
+    pub name: &'static str,
+    /// The JSON type and how the value is encoded
+    pub encoding: &'static str,
+    /// What the field contains
+    pub description: &'static str,
+}
+
+pub(crate) const POINT_ENCODING: &str = "string, base64url without padding of the point bytes";
+pub(crate) const POINT_ARRAY_ENCODING: &str =
+    "array of strings, each base64url without padding of the point bytes";
+pub(crate) const SHARE_ENCODING: &str =
+    "array of numbers 0-255, the first is the share identifier followed by the scalar bytes";
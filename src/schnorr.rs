@@ -0,0 +1,79 @@
+use crate::{
+    deserialize_g, deserialize_scalar, serialize_g, serialize_scalar, DkgHasher, Sha256Hasher,
+};
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use vsss_rs::elliptic_curve::{ff::Field, group::GroupEncoding, Group};
+
+const CHALLENGE_DOMAIN: &[u8] = b"gennaro-dkg schnorr challenge";
+
+/// A Schnorr signature over the DKG curve using the default generator
+///
+/// The challenge is derived from the SHA-256 hash of the commitment,
+/// the public key and the message.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct SchnorrSignature<G: Group + GroupEncoding + Default> {
+    /// The nonce commitment
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub commitment: G,
+    /// The response
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub response: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> SchnorrSignature<G> {
+    /// Sign `message` with `secret_key`
+    pub fn sign(secret_key: &G::Scalar, message: &[u8], rng: impl RngCore + CryptoRng) -> Self {
+        Self::sign_with_generator(G::generator(), secret_key, message, rng)
+    }
+
+    /// Check this is a signature over `message` from the owner of `public_key`
+    pub fn verify(&self, public_key: G, message: &[u8]) -> bool {
+        self.verify_with_generator(G::generator(), public_key, message)
+    }
+
+    /// Sign `message` with `secret_key` where the public key is `generator * secret_key`
+    pub(crate) fn sign_with_generator(
+        generator: G,
+        secret_key: &G::Scalar,
+        message: &[u8],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Self {
+        let nonce = G::Scalar::random(&mut rng);
+        let commitment = generator * nonce;
+        let public_key = generator * secret_key;
+        let challenge = Self::challenge(commitment, public_key, message);
+        Self {
+            commitment,
+            response: nonce + challenge * secret_key,
+        }
+    }
+
+    /// Check this is a signature over `message` from the owner of `public_key`
+    /// where the public key is relative to `generator`
+    pub(crate) fn verify_with_generator(
+        &self,
+        generator: G,
+        public_key: G,
+        message: &[u8],
+    ) -> bool {
+        if (public_key.is_identity() | self.commitment.is_identity()).into() {
+            return false;
+        }
+        let challenge = Self::challenge(self.commitment, public_key, message);
+        generator * self.response == self.commitment + public_key * challenge
+    }
+
+    fn challenge(commitment: G, public_key: G, message: &[u8]) -> G::Scalar {
+        let seed = Sha256Hasher::digest(&[
+            CHALLENGE_DOMAIN,
+            commitment.to_bytes().as_ref(),
+            public_key.to_bytes().as_ref(),
+            message,
+        ]);
+        G::Scalar::random(rand_chacha::ChaChaRng::from_seed(seed))
+    }
+}
@@ -1,18 +1,15 @@
+use crate::SecretStore;
 use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
-use soteria_rs::Protected;
-use std::sync::{Arc, Mutex};
+use zeroize::Zeroizing;
 
-pub fn serialize<S: Serializer>(input: &Arc<Mutex<Protected>>, s: S) -> Result<S::Ok, S::Error> {
-    let mut protected = input
-        .lock()
-        .map_err(|_e| ser::Error::custom("unable to acquire lock".to_string()))?;
-    let unprotected = protected
+pub fn serialize<T: SecretStore, S: Serializer>(input: &T, s: S) -> Result<S::Ok, S::Error> {
+    let unprotected = input
         .unprotect()
         .ok_or_else(|| ser::Error::custom("invalid secret"))?;
-    unprotected.as_ref().serialize(s)
+    unprotected.as_slice().serialize(s)
 }
 
-pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Arc<Mutex<Protected>>, D::Error> {
-    let input = Vec::<u8>::deserialize(d)?;
-    Ok(Arc::new(Mutex::new(Protected::new(input.as_slice()))))
+pub fn deserialize<'de, T: SecretStore, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+    let input = Zeroizing::new(Vec::<u8>::deserialize(d)?);
+    Ok(T::protect(input.as_slice()))
 }
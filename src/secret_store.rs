@@ -0,0 +1,145 @@
+use crate::{DkgResult, Round1P2PData};
+use soteria_rs::Protected;
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex},
+};
+use uint_zigzag::Uint;
+use vsss_rs::elliptic_curve::PrimeField;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Storage for the secret values a participant holds between rounds.
+///
+/// The default is [`ProtectedStore`] which keeps secrets encrypted in memory
+/// using soteria. [`InMemoryStore`] keeps them as plain bytes that are
+/// zeroized on drop for platforms where that is not an option.
+pub trait SecretStore: Clone + Debug {
+    /// Place `secret` into storage
+    fn protect(secret: &[u8]) -> Self;
+    /// Retrieve a copy of the stored secret.
+    ///
+    /// Returns [`None`] if the secret is unavailable or has been tampered with.
+    fn unprotect(&self) -> Option<Zeroizing<Vec<u8>>>;
+    /// Erase the stored secret so it can no longer be retrieved
+    fn wipe(&mut self);
+}
+
+/// Receives the final secret share when round 4 completes,
+/// see [`Participant::round4_with_sink`](crate::Participant::round4_with_sink).
+///
+/// A sink backed by an HSM can upload the share and keep only a handle to it
+/// so the share does not stay in process memory.
+pub trait ShareSink<F: PrimeField> {
+    /// Take the final secret share
+    fn store(&mut self, share: F) -> DkgResult<()>;
+}
+
+/// A [`ShareSink`] that keeps the share in memory in a [`SecretStore`]
+#[derive(Clone, Debug)]
+pub struct MemoryShareSink<S: SecretStore = ProtectedStore>(Option<S>);
+
+impl<S: SecretStore> Default for MemoryShareSink<S> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<S: SecretStore> MemoryShareSink<S> {
+    /// The stored share or [`None`] if nothing was stored
+    pub fn share<F: PrimeField>(&self) -> Option<F> {
+        self.0.as_ref().and_then(unprotect_scalar)
+    }
+}
+
+impl<F: PrimeField, S: SecretStore> ShareSink<F> for MemoryShareSink<S> {
+    fn store(&mut self, share: F) -> DkgResult<()> {
+        if let Some(mut old) = self.0.replace(protect_scalar(&share)) {
+            old.wipe();
+        }
+        Ok(())
+    }
+}
+
+/// Secrets protected in memory by soteria that can be shared across threads
+#[derive(Clone, Debug)]
+pub struct ProtectedStore(Arc<Mutex<Protected>>);
+
+impl SecretStore for ProtectedStore {
+    fn protect(secret: &[u8]) -> Self {
+        Self(Arc::new(Mutex::new(Protected::new(secret))))
+    }
+
+    fn unprotect(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let mut protected = self.0.lock().ok()?;
+        let unprotected = protected.unprotect()?;
+        Some(Zeroizing::new(unprotected.as_ref().to_vec()))
+    }
+
+    /// Erases the secret for every clone of this store
+    fn wipe(&mut self) {
+        if let Ok(mut protected) = self.0.lock() {
+            *protected = Protected::new([]);
+        }
+    }
+}
+
+/// Secrets held as plain bytes that are zeroized when dropped
+#[derive(Clone)]
+pub struct InMemoryStore(Zeroizing<Vec<u8>>);
+
+impl Debug for InMemoryStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "InMemoryStore(<redacted>)")
+    }
+}
+
+impl SecretStore for InMemoryStore {
+    fn protect(secret: &[u8]) -> Self {
+        Self(Zeroizing::new(secret.to_vec()))
+    }
+
+    fn unprotect(&self) -> Option<Zeroizing<Vec<u8>>> {
+        Some(self.0.clone())
+    }
+
+    fn wipe(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+pub(crate) fn protect_scalar<S: SecretStore, F: PrimeField>(scalar: &F) -> S {
+    S::protect(scalar.to_repr().as_ref())
+}
+
+pub(crate) fn unprotect_scalar<S: SecretStore, F: PrimeField>(store: &S) -> Option<F> {
+    let bytes = store.unprotect()?;
+    let mut repr = F::Repr::default();
+    if repr.as_ref().len() != bytes.len() {
+        return None;
+    }
+    repr.as_mut().copy_from_slice(&bytes);
+    Option::from(F::from_repr(repr))
+}
+
+/// Stored as the length of the secret share followed by
+/// the secret share and blind share bytes
+pub(crate) fn protect_p2p<S: SecretStore>(data: &Round1P2PData) -> S {
+    let mut bytes = Zeroizing::new(Uint::from(data.secret_share.len()).to_vec());
+    bytes.extend_from_slice(&data.secret_share);
+    bytes.extend_from_slice(&data.blind_share);
+    S::protect(&bytes)
+}
+
+pub(crate) fn unprotect_p2p<S: SecretStore>(store: &S) -> Option<Round1P2PData> {
+    let bytes = store.unprotect()?;
+    let length_size = Uint::peek(bytes.as_slice())?;
+    let length = Uint::try_from(&bytes[..length_size]).ok()?.0 as usize;
+    let shares = &bytes[length_size..];
+    if shares.len() < length {
+        return None;
+    }
+    Some(Round1P2PData {
+        secret_share: shares[..length].to_vec(),
+        blind_share: shares[length..].to_vec(),
+    })
+}
@@ -0,0 +1,145 @@
+//! Helpers for testing code built on top of the DKG.
+//!
+//! These reconstruct the secret so they must never be used in production.
+
+use crate::*;
+use vsss_rs::combine_shares;
+
+/// Reconstruct the secret from the shares of the participants in `ids`.
+///
+/// Every id must belong to one of `participants` and that participant must
+/// have completed the protocol. The result is only the secret if at least
+/// `threshold` valid participants are used.
+///
+/// Throws an error if `ids` is below the threshold or contains duplicates,
+/// an id has no matching participant, or a participant has not completed.
+pub fn reconstruct_secret<I, G, S>(
+    participants: &[&Participant<I, G, S>],
+    ids: &[usize],
+) -> DkgResult<G::Scalar>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+{
+    let unique = ids.iter().copied().collect::<BTreeSet<_>>();
+    if unique.len() != ids.len() {
+        return Err(Error::InvalidInput("duplicate ids".to_string()));
+    }
+    let mut shares = Vec::with_capacity(ids.len());
+    for id in ids {
+        let participant = participants
+            .iter()
+            .find(|p| p.get_id() == *id)
+            .ok_or_else(|| Error::InvalidInput(format!("no participant with id {}", id)))?;
+        if ids.len() < participant.get_threshold() {
+            return Err(Error::InsufficientParticipants {
+                have: ids.len(),
+                need: participant.get_threshold(),
+            });
+        }
+        shares.push(encode_share(*id, participant.try_get_secret_share()?)?);
+    }
+    Ok(combine_shares::<G::Scalar, u8, Vec<u8>>(&shares)?)
+}
+
+/// Run round 1 for every participant, returning the broadcast and
+/// peer-to-peer data keyed by the sender's id
+#[cfg(test)]
+pub(crate) fn run_round1<
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+>(
+    participants: &mut [Participant<I, G, S>],
+) -> (
+    BTreeMap<usize, Round1BroadcastData<G>>,
+    BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+) {
+    let mut r1bdata = BTreeMap::new();
+    let mut r1p2pdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = p.round1().unwrap();
+        r1bdata.insert(p.get_id(), bdata);
+        r1p2pdata.insert(p.get_id(), p2p);
+    }
+    (r1bdata, r1p2pdata)
+}
+
+/// Select the round 1 data addressed to `my_id`
+#[cfg(test)]
+pub(crate) fn round2_inputs<G: Group + GroupEncoding + Default>(
+    my_id: usize,
+    r1bdata: &BTreeMap<usize, Round1BroadcastData<G>>,
+    r1p2pdata: &BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+) -> (
+    BTreeMap<usize, Round1BroadcastData<G>>,
+    BTreeMap<usize, Round1P2PData>,
+) {
+    let bdata = r1bdata
+        .iter()
+        .filter(|(id, _)| **id != my_id)
+        .map(|(id, b)| (*id, b.clone()))
+        .collect();
+    let p2p = r1p2pdata
+        .iter()
+        .filter(|(id, _)| **id != my_id)
+        .map(|(id, p)| (*id, p[&my_id].clone()))
+        .collect();
+    (bdata, p2p)
+}
+
+/// Create participants `1..=limit` sharing the same parameters
+#[cfg(test)]
+pub(crate) fn new_participants<G: Group + GroupEncoding + Default>(
+    threshold: usize,
+    limit: usize,
+) -> Vec<SecretParticipant<G>> {
+    let parameters = Parameters::<G>::new(
+        NonZeroUsize::new(threshold).unwrap(),
+        NonZeroUsize::new(limit).unwrap(),
+    );
+    (1..=limit)
+        .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect()
+}
+
+/// Create participants and run them through round 5
+#[cfg(test)]
+pub(crate) fn run_dkg<G: Group + GroupEncoding + Default>(
+    threshold: usize,
+    limit: usize,
+) -> Vec<SecretParticipant<G>> {
+    let mut participants = new_participants::<G>(threshold, limit);
+    complete_dkg(&mut participants);
+    participants
+}
+
+/// Run `participants` from round 1 through round 5
+#[cfg(test)]
+pub(crate) fn complete_dkg<
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+>(
+    participants: &mut [Participant<I, G, S>],
+) {
+    let (r1bdata, r1p2pdata) = run_round1(participants);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    let mut r4bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+    }
+    for p in participants.iter() {
+        p.round5(&r4bdata).unwrap();
+    }
+}
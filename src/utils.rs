@@ -0,0 +1,244 @@
+use crate::{DkgResult, Error, Parameters, Round3BroadcastData, MAX_PARTICIPANTS};
+use rand_core::RngCore;
+use std::collections::{BTreeMap, BTreeSet};
+use vsss_rs::{
+    elliptic_curve::{ff::Field, group::GroupEncoding, Group, PrimeField},
+    Share,
+};
+
+/// Check a share and blind share received from a dealer against
+/// the dealer's round 1 pedersen commitments.
+///
+/// `id` is the identifier of the participant that received the shares
+/// and must match the identifier in both shares. The generators are the
+/// ones the dealer advertised in [`crate::Round1BroadcastData`].
+///
+/// This is the same check round 2 performs, exposed for auditing stored shares.
+pub fn verify_share<G: Group + GroupEncoding + Default>(
+    share: &[u8],
+    blind_share: &[u8],
+    pedersen_commitments: &[G],
+    id: usize,
+    message_generator: G,
+    blinder_generator: G,
+) -> bool {
+    let (share_id, secret) = match parse_share::<G::Scalar>(share) {
+        Some(s) => s,
+        None => return false,
+    };
+    let (blind_id, blinder) = match parse_share::<G::Scalar>(blind_share) {
+        Some(s) => s,
+        None => return false,
+    };
+    if id == 0 || share_id as usize != id || blind_id as usize != id {
+        return false;
+    }
+    if pedersen_commitments.is_empty() {
+        return false;
+    }
+
+    message_generator * secret + blinder_generator * blinder
+        == evaluate_public_polynomial(pedersen_commitments, id)
+}
+
+/// Compute the Lagrange coefficients at zero for the participants in `ids`.
+///
+/// A participant's id is the x-coordinate of its share, the same
+/// value as the share identifier, so participant 1 holds the evaluation at 1.
+/// The secret is the sum of each share multiplied by its coefficient.
+///
+/// Throws an error if `ids` is empty, contains zero, or contains duplicates.
+pub fn lagrange_coefficients<G: Group + GroupEncoding + Default>(
+    ids: &[usize],
+) -> DkgResult<BTreeMap<usize, G::Scalar>> {
+    lagrange_coefficients_at::<G>(ids, 0)
+}
+
+/// Compute the Lagrange coefficients at `x` for the participants in `ids`
+pub(crate) fn lagrange_coefficients_at<G: Group + GroupEncoding + Default>(
+    ids: &[usize],
+    x: usize,
+) -> DkgResult<BTreeMap<usize, G::Scalar>> {
+    if ids.is_empty() {
+        return Err(Error::InvalidInput("no ids provided".to_string()));
+    }
+    if ids.contains(&0) {
+        return Err(Error::InvalidInput("ids cannot be zero".to_string()));
+    }
+    if ids.iter().collect::<BTreeSet<_>>().len() != ids.len() {
+        return Err(Error::InvalidInput("ids must be unique".to_string()));
+    }
+
+    let x = G::Scalar::from(x as u64);
+    let mut coefficients = BTreeMap::new();
+    for i in ids {
+        let x_i = G::Scalar::from(*i as u64);
+        let mut numerator = G::Scalar::ONE;
+        let mut denominator = G::Scalar::ONE;
+        for j in ids.iter().filter(|j| *j != i) {
+            let x_j = G::Scalar::from(*j as u64);
+            numerator *= x_j - x;
+            denominator *= x_j - x_i;
+        }
+        let inverse = Option::<G::Scalar>::from(denominator.invert())
+            .ok_or_else(|| Error::InvalidInput("ids are not distinct in the field".to_string()))?;
+        coefficients.insert(*i, numerator * inverse);
+    }
+    Ok(coefficients)
+}
+
+/// Recompute the group public key from the round 3 broadcasts of
+/// the valid participants and compare it to `claimed_public_key`.
+///
+/// This needs no secret state so light clients and auditors that only
+/// see broadcast data can check the result of the protocol.
+///
+/// Throws an error if fewer than threshold broadcasts are provided
+/// or if any broadcast contains malformed commitments.
+pub fn verify_public_key<G: Group + GroupEncoding + Default>(
+    parameters: &Parameters<G>,
+    round3_broadcasts: &BTreeMap<usize, Round3BroadcastData<G>>,
+    claimed_public_key: G,
+) -> DkgResult<bool> {
+    if round3_broadcasts.len() < parameters.threshold {
+        return Err(Error::InsufficientParticipants {
+            have: round3_broadcasts.len(),
+            need: parameters.threshold,
+        });
+    }
+    let mut public_key = G::identity();
+    for (id, bdata) in round3_broadcasts {
+        if bdata.commitments.len() != parameters.threshold
+            || bdata
+                .commitments
+                .iter()
+                .skip(1)
+                .any(|c| c.is_identity().into())
+        {
+            return Err(Error::InvalidCommitment { from: *id });
+        }
+        public_key += bdata.commitments[0];
+    }
+    Ok(public_key == claimed_public_key)
+}
+
+/// Sum the group public keys of independent DKGs into one combined key.
+///
+/// The secret shares of the combined key are the sums of each participant's
+/// shares, see [`crate::Participant::add_share_from`]. This only works when
+/// every DKG used the same participant ids and threshold.
+pub fn aggregate_public_keys<G: Group>(keys: &[G]) -> G {
+    keys.iter().fold(G::identity(), |acc, k| acc + k)
+}
+
+/// Evaluate the polynomial in the exponent defined by `commitments` at `x`,
+/// i.e. compute `∏ C_j^{x^j}` using Horner's method.
+///
+/// With the feldman commitments from [`Participant::get_public_commitments`]
+/// this is the expected public share `g^{share_x}` of participant `x`.
+pub fn evaluate_public_polynomial<G: Group>(commitments: &[G], x: usize) -> G {
+    let x = G::Scalar::from(x as u64);
+    commitments
+        .iter()
+        .rev()
+        .fold(G::identity(), |acc, c| acc * x + c)
+}
+
+/// Recover the commitments to the coefficients of the polynomial in the
+/// exponent that passes through `points`, the inverse of [`evaluate_public_polynomial`].
+///
+/// Each point maps a participant id to its public share `g^{f(id)}`, so
+/// `threshold` points recover the combined feldman commitments and the
+/// first commitment is the public key, i.e. the interpolation at zero.
+///
+/// Throws an error if `points` is empty or contains the id zero.
+pub(crate) fn interpolate_public_polynomial<G: Group + GroupEncoding + Default>(
+    points: &BTreeMap<usize, G>,
+) -> DkgResult<Vec<G>> {
+    let ids = points.keys().copied().collect::<Vec<_>>();
+    // Rejects empty and zero ids, the keys are already unique
+    lagrange_coefficients::<G>(&ids)?;
+
+    let mut commitments = vec![G::identity(); ids.len()];
+    for (i, point) in points {
+        // Expand the Lagrange basis polynomial ∏ (x - x_j) / (x_i - x_j)
+        let x_i = G::Scalar::from(*i as u64);
+        let mut basis = vec![G::Scalar::ONE];
+        let mut denominator = G::Scalar::ONE;
+        for j in ids.iter().filter(|j| *j != i) {
+            let x_j = G::Scalar::from(*j as u64);
+            let mut next = vec![G::Scalar::ZERO; basis.len() + 1];
+            for (k, b) in basis.iter().enumerate() {
+                next[k] -= *b * x_j;
+                next[k + 1] += b;
+            }
+            basis = next;
+            denominator *= x_i - x_j;
+        }
+        let inverse = Option::<G::Scalar>::from(denominator.invert())
+            .ok_or_else(|| Error::InvalidInput("ids are not distinct in the field".to_string()))?;
+        for (c, b) in commitments.iter_mut().zip(basis.iter()) {
+            *c += *point * (*b * inverse);
+        }
+    }
+    Ok(commitments)
+}
+
+/// Encode the share of participant `id` in the format used by every share
+/// in this crate: one byte holding `id` followed by the `PrimeField::to_repr`
+/// bytes of `value`.
+///
+/// This is the same layout `vsss_rs::combine_shares` expects for `Vec<u8>`
+/// shares on every curve, so the shares sent in [`crate::Round1P2PData`]
+/// and the ones built from [`crate::Participant::get_secret_share`] can be combined.
+///
+/// Throws an error if `id` is zero or larger than [`MAX_PARTICIPANTS`].
+pub fn encode_share<F: PrimeField>(id: usize, value: F) -> DkgResult<Vec<u8>> {
+    if id == 0 || id > MAX_PARTICIPANTS {
+        return Err(Error::InvalidInput(format!(
+            "share id {} must be between 1 and {}",
+            id, MAX_PARTICIPANTS
+        )));
+    }
+    Ok(Vec::<u8>::from_field_element(id as u8, value)?)
+}
+
+/// Decode a share created with [`encode_share`] into its id and value.
+///
+/// Throws an error if the id is zero, the length does not match
+/// the scalar encoding, or the bytes are not a canonical scalar.
+pub fn decode_share<F: PrimeField>(share: &[u8]) -> DkgResult<(usize, F)> {
+    parse_share(share)
+        .filter(|(id, _)| *id != 0)
+        .map(|(id, value)| (id as usize, value))
+        .ok_or_else(|| Error::InvalidInput("invalid share encoding".to_string()))
+}
+
+fn parse_share<F: PrimeField>(share: &[u8]) -> Option<(u8, F)> {
+    let (id, value) = share.split_first()?;
+    let mut repr = F::Repr::default();
+    if repr.as_ref().len() != value.len() {
+        return None;
+    }
+    repr.as_mut().copy_from_slice(value);
+    Option::<F>::from(F::from_repr(repr)).map(|f| (*id, f))
+}
+
+/// Check that `point` is in the prime-order subgroup.
+///
+/// Multiplying by the scalar `-1` multiplies by `q - 1`, so adding `point`
+/// gives `q * point` which is the identity only for points in the subgroup.
+/// Unlike `CofactorGroup::is_torsion_free` this works for every group.
+pub(crate) fn in_prime_order_subgroup<G: Group>(point: G) -> bool {
+    (point * -G::Scalar::ONE + point).is_identity().into()
+}
+
+/// Sample a point from `rng` that is in the prime-order subgroup and not the identity
+pub(crate) fn random_subgroup_point<G: Group>(mut rng: impl RngCore) -> G {
+    loop {
+        let point = G::random(&mut rng);
+        if !bool::from(point.is_identity()) && in_prime_order_subgroup(point) {
+            return point;
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Helpers shared by the integration tests
+
+#![allow(dead_code)]
+
+use gennaro_dkg::*;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+pub fn run_round1<
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+>(
+    participants: &mut [Participant<I, G, S>],
+) -> (
+    BTreeMap<usize, Round1BroadcastData<G>>,
+    BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+) {
+    let mut r1bdata = BTreeMap::new();
+    let mut r1p2pdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = p.round1().unwrap();
+        r1bdata.insert(p.get_id(), bdata);
+        r1p2pdata.insert(p.get_id(), p2p);
+    }
+    (r1bdata, r1p2pdata)
+}
+
+pub fn round2_inputs<G: Group + GroupEncoding + Default>(
+    my_id: usize,
+    r1bdata: &BTreeMap<usize, Round1BroadcastData<G>>,
+    r1p2pdata: &BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+) -> (
+    BTreeMap<usize, Round1BroadcastData<G>>,
+    BTreeMap<usize, Round1P2PData>,
+) {
+    let bdata = r1bdata
+        .iter()
+        .filter(|(id, _)| **id != my_id)
+        .map(|(id, b)| (*id, b.clone()))
+        .collect();
+    let p2p = r1p2pdata
+        .iter()
+        .filter(|(id, _)| **id != my_id)
+        .map(|(id, p)| (*id, p[&my_id].clone()))
+        .collect();
+    (bdata, p2p)
+}
+
+pub fn new_participants<G: Group + GroupEncoding + Default>(
+    threshold: usize,
+    limit: usize,
+) -> Vec<SecretParticipant<G>> {
+    let parameters = Parameters::<G>::new(
+        NonZeroUsize::new(threshold).unwrap(),
+        NonZeroUsize::new(limit).unwrap(),
+    );
+    (1..=limit)
+        .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect()
+}
+
+pub fn run_dkg<G: Group + GroupEncoding + Default>(
+    threshold: usize,
+    limit: usize,
+) -> Vec<SecretParticipant<G>> {
+    let mut participants = new_participants::<G>(threshold, limit);
+    complete_dkg(&mut participants);
+    participants
+}
+
+pub fn complete_dkg<
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+    S: SecretStore,
+>(
+    participants: &mut [Participant<I, G, S>],
+) {
+    let (r1bdata, r1p2pdata) = run_round1(participants);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    let mut r4bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+    }
+    for p in participants.iter() {
+        p.round5(&r4bdata).unwrap();
+    }
+}
@@ -33,6 +33,12 @@ mod init_dkg {
         five_participants_init::<bls12_381_plus::G1Projective>();
         five_participants_init::<bls12_381_plus::G2Projective>();
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_init::<pasta_curves::pallas::Point>();
+        five_participants_init::<pasta_curves::vesta::Point>();
+    }
 }
 
 // Previous threshold was 3
@@ -61,6 +67,12 @@ mod add_participant_same_threshold {
         five_participants_add_participant::<bls12_381_plus::G1Projective>(3);
         five_participants_add_participant::<bls12_381_plus::G2Projective>(3);
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_add_participant::<pasta_curves::pallas::Point>(3);
+        five_participants_add_participant::<pasta_curves::vesta::Point>(3);
+    }
 }
 
 // Previous threshold was 3, new threshold is 5
@@ -89,6 +101,12 @@ mod add_participant_increase_threshold {
         five_participants_add_participant::<bls12_381_plus::G1Projective>(5);
         five_participants_add_participant::<bls12_381_plus::G2Projective>(4);
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_add_participant::<pasta_curves::pallas::Point>(5);
+        five_participants_add_participant::<pasta_curves::vesta::Point>(5);
+    }
 }
 
 // Previous threshold was 3
@@ -117,6 +135,12 @@ mod remove_participant_same_threshold {
         five_participants_remove_participant::<bls12_381_plus::G1Projective>(3);
         five_participants_remove_participant::<bls12_381_plus::G2Projective>(3);
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_remove_participant::<pasta_curves::pallas::Point>(3);
+        five_participants_remove_participant::<pasta_curves::vesta::Point>(3);
+    }
 }
 
 // Previous threshold was 3, new threshold is 2
@@ -145,6 +169,12 @@ mod remove_participant_decrease_threshold {
         five_participants_remove_participant::<bls12_381_plus::G1Projective>(2);
         five_participants_remove_participant::<bls12_381_plus::G2Projective>(2);
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_remove_participant::<pasta_curves::pallas::Point>(2);
+        five_participants_remove_participant::<pasta_curves::vesta::Point>(2);
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +202,12 @@ mod add_and_remove_participant_increase_participant {
         five_participants_add_and_remove_increase_participant::<bls12_381_plus::G1Projective>(5);
         five_participants_add_and_remove_increase_participant::<bls12_381_plus::G2Projective>(2);
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_add_and_remove_increase_participant::<pasta_curves::pallas::Point>(5);
+        five_participants_add_and_remove_increase_participant::<pasta_curves::vesta::Point>(5);
+    }
 }
 
 #[cfg(test)]
@@ -199,6 +235,12 @@ mod add_and_remove_participant_decrease_participant {
         five_participants_add_and_remove_decrease_participant::<bls12_381_plus::G1Projective>(3);
         five_participants_add_and_remove_decrease_participant::<bls12_381_plus::G2Projective>(4);
     }
+
+    #[test]
+    fn five_participants_pasta() {
+        five_participants_add_and_remove_decrease_participant::<pasta_curves::pallas::Point>(3);
+        five_participants_add_and_remove_decrease_participant::<pasta_curves::vesta::Point>(3);
+    }
 }
 
 fn five_participants_init<G: Group + GroupEncoding + Default>(
@@ -0,0 +1,384 @@
+mod common;
+
+use common::*;
+use gennaro_dkg::*;
+use std::num::NonZeroUsize;
+use vsss_rs::{combine_shares, elliptic_curve::PrimeField, Share};
+use zeroize::Zeroize;
+
+#[test]
+fn export_key_package() {
+    let participants = run_dkg::<k256::ProjectivePoint>(2, 3);
+    assert!(new_participants::<k256::ProjectivePoint>(2, 3)[0]
+        .export()
+        .is_err());
+
+    for p in &participants {
+        let package = p.export().unwrap();
+        assert_eq!(package.identifier, p.get_id());
+        assert_eq!(package.public_key, p.get_public_key().unwrap());
+        assert_eq!(package.verification_shares.len(), 3);
+        for q in &participants {
+            assert_eq!(
+                package.verification_shares[&q.get_id()],
+                k256::ProjectivePoint::GENERATOR * q.get_secret_share().unwrap()
+            );
+        }
+
+        let json = serde_json::to_string(&package).unwrap();
+        let package2 = serde_json::from_str::<KeyPackage<k256::ProjectivePoint>>(&json).unwrap();
+        assert_eq!(package.secret_share, package2.secret_share);
+        assert_eq!(package.verification_shares, package2.verification_shares);
+
+        let bin = serde_bare::to_vec(&package).unwrap();
+        let package2 = serde_bare::from_slice::<KeyPackage<k256::ProjectivePoint>>(&bin).unwrap();
+        assert_eq!(package.public_key, package2.public_key);
+        assert_eq!(package.verification_shares, package2.verification_shares);
+
+        let debug = format!("{:?}", package);
+        assert!(debug.contains("<redacted>"));
+        assert!(!debug.contains(&format!("{:?}", package.secret_share)));
+        let mut package = package;
+        package.zeroize();
+        assert_eq!(package.secret_share, k256::Scalar::ZERO);
+    }
+}
+
+#[test]
+fn export_public_key_package() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(2, 3);
+    assert!(matches!(
+        new_participants::<G>(2, 3)[0].public_key_package(),
+        Err(Error::NotComplete)
+    ));
+
+    let package = participants[0].public_key_package().unwrap();
+    assert_eq!(
+        package.public_key,
+        participants[0].get_public_key().unwrap()
+    );
+    assert_eq!(
+        package.verification_shares,
+        participants[0].export().unwrap().verification_shares
+    );
+    assert!(package
+        .parameters
+        .validate_against(&participants[1].get_parameters())
+        .is_ok());
+
+    let json = serde_json::to_string(&package).unwrap();
+    for p in &participants {
+        let share = p.get_secret_share().unwrap();
+        assert!(!json.contains(&data_encoding::BASE64URL_NOPAD.encode(&share.to_repr())));
+    }
+    let package2 = serde_json::from_str::<PublicKeyPackage<G>>(&json).unwrap();
+    assert_eq!(package.public_key, package2.public_key);
+    assert_eq!(package.verification_shares, package2.verification_shares);
+
+    let bin = serde_bare::to_vec(&package).unwrap();
+    let package2 = serde_bare::from_slice::<PublicKeyPackage<G>>(&bin).unwrap();
+    assert_eq!(package.public_key, package2.public_key);
+    assert_eq!(package.verification_shares, package2.verification_shares);
+    assert!(package2
+        .parameters
+        .validate_against(&package.parameters)
+        .is_ok());
+}
+
+#[cfg(feature = "curve25519")]
+#[test]
+fn public_key_and_secret_share_bytes() {
+    use vsss_rs::elliptic_curve::group::GroupEncoding;
+    type G = vsss_rs::curve25519::WrappedEdwards;
+    let mut participants = new_participants::<G>(2, 3);
+    assert!(participants[0].get_public_key_bytes().is_none());
+    assert!(participants[0].get_secret_share_bytes().is_none());
+    complete_dkg(&mut participants);
+
+    let pk = participants[0].get_public_key_bytes().unwrap();
+    assert_eq!(pk.len(), 32);
+    assert_eq!(
+        pk,
+        participants[0]
+            .get_public_key()
+            .unwrap()
+            .to_bytes()
+            .as_ref()
+            .to_vec()
+    );
+    let share = participants[0].get_secret_share_bytes().unwrap();
+    assert_eq!(
+        share.as_slice(),
+        participants[0]
+            .get_secret_share()
+            .unwrap()
+            .to_repr()
+            .as_ref()
+    );
+}
+
+#[test]
+fn debug_redacts_secrets() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    complete_dkg(&mut participants);
+    let output = format!("{:?}", participants[0]);
+    assert!(output.contains("<redacted>"));
+    assert!(output.contains("valid_participant_ids"));
+    let share = participants[0].get_secret_share().unwrap();
+    assert!(!output.contains(&format!("{:?}", share)));
+}
+
+#[test]
+fn additive_shares() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(3, 5);
+    let public_key = participants[0].get_public_key().unwrap();
+    let quorum = maplit::btreeset! {1, 3, 5};
+
+    let sum = participants
+        .iter()
+        .filter(|p| quorum.contains(&p.get_id()))
+        .map(|p| p.to_additive_share(&quorum).unwrap())
+        .reduce(|acc, s| acc + s)
+        .unwrap();
+    assert_eq!(G::GENERATOR * sum, public_key);
+
+    assert!(participants[1].to_additive_share(&quorum).is_err());
+    assert!(matches!(
+        participants[0].to_additive_share(&maplit::btreeset! {1, 3}),
+        Err(Error::InsufficientParticipants { have: 2, need: 3 })
+    ));
+    assert!(participants[0]
+        .to_additive_share(&maplit::btreeset! {1, 3, 6})
+        .is_err());
+    assert!(matches!(
+        new_participants::<G>(3, 5)[0].to_additive_share(&quorum),
+        Err(Error::NotComplete)
+    ));
+}
+
+#[test]
+fn proof_of_possession() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(2, 3);
+    let pops = participants
+        .iter()
+        .map(|p| p.round6_pop().unwrap())
+        .collect::<Vec<_>>();
+    for p in &participants {
+        for pop in &pops {
+            p.verify_pop(pop).unwrap();
+        }
+    }
+
+    let mut forged = pops[1];
+    forged.id = 3;
+    assert!(matches!(
+        participants[0].verify_pop(&forged),
+        Err(Error::BadSignature { from: 3 })
+    ));
+    forged.id = 4;
+    assert!(participants[0].verify_pop(&forged).is_err());
+    assert!(matches!(
+        new_participants::<G>(2, 3)[0].round6_pop(),
+        Err(Error::NotComplete)
+    ));
+}
+
+#[test]
+fn refresh_delta() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(2, 3);
+    let parameters = participants[0].get_parameters();
+    let mut refresh = (1..=3)
+        .map(|id| RefreshParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        refresh[0].refresh_delta(),
+        Err(Error::NotComplete)
+    ));
+    complete_dkg(&mut refresh);
+
+    let shares = participants
+        .iter()
+        .zip(refresh.iter())
+        .map(|(p, r)| {
+            let delta = r.refresh_delta().unwrap();
+            assert_eq!(delta, r.get_secret_share().unwrap());
+            <Vec<u8> as Share>::from_field_element(
+                p.get_id() as u8,
+                p.get_secret_share().unwrap() + delta,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares[1..]).unwrap();
+    assert_eq!(
+        G::GENERATOR * secret,
+        participants[0].get_public_key().unwrap()
+    );
+}
+
+#[test]
+fn key_fingerprint() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(2, 3);
+    let fingerprint = participants[0].key_fingerprint().unwrap();
+    for p in &participants {
+        assert_eq!(p.key_fingerprint(), Some(fingerprint));
+    }
+    let other = run_dkg::<G>(2, 3);
+    assert_ne!(other[0].key_fingerprint(), Some(fingerprint));
+    assert_eq!(new_participants::<G>(2, 3)[0].key_fingerprint(), None);
+
+    struct OnesHasher;
+    impl DkgHasher for OnesHasher {
+        fn digest(_inputs: &[&[u8]]) -> [u8; 32] {
+            [1u8; 32]
+        }
+    }
+    assert_eq!(
+        participants[0].key_fingerprint_with::<Sha256Hasher>(),
+        Some(fingerprint)
+    );
+    assert_eq!(
+        participants[0].key_fingerprint_with::<OnesHasher>(),
+        Some([1u8; 16])
+    );
+}
+
+#[test]
+fn downgrade_threshold() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(3, 4);
+    let public_key = participants[0].get_public_key().unwrap();
+    let secret_shares = participants
+        .iter()
+        .map(|p| {
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&secret_shares[..3]).unwrap();
+
+    let two = NonZeroUsize::new(2).unwrap();
+    let quorum = maplit::btreeset! {1, 2, 3, 4};
+    let session = b"downgrade to 2";
+    assert!(participants[0]
+        .downgrade_threshold(NonZeroUsize::new(3).unwrap(), &quorum, session)
+        .is_err());
+    assert!(participants[0]
+        .downgrade_threshold(NonZeroUsize::new(1).unwrap(), &quorum, session)
+        .is_err());
+    assert!(participants[0]
+        .downgrade_threshold(two, &maplit::btreeset! {1, 2}, session)
+        .is_err());
+    let mut downgraded = participants
+        .iter()
+        .map(|p| p.downgrade_threshold(two, &quorum, session).unwrap())
+        .collect::<Vec<_>>();
+    complete_dkg(&mut downgraded);
+
+    // The new session has fresh parameters that every member agrees on
+    let parameters = participants[0].get_parameters();
+    let expected = ParametersBuilder::<G>::new()
+        .threshold(2)
+        .limit(4)
+        .session_id(session)
+        .build()
+        .unwrap();
+    for p in &downgraded {
+        assert_eq!(p.get_public_key().unwrap(), public_key);
+        assert_eq!(p.get_threshold(), 2);
+        assert_eq!(p.get_parameters().to_bytes(), expected.to_bytes());
+        assert_ne!(p.get_parameters().fingerprint(), parameters.fingerprint());
+    }
+    let shares = downgraded
+        .iter()
+        .map(|p| {
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares[2..]).unwrap(),
+        secret
+    );
+}
+
+#[test]
+fn tweak_add() {
+    type G = k256::ProjectivePoint;
+    let mut participants = run_dkg::<G>(2, 3);
+    let tweak = k256::Scalar::from(12345u64);
+    let mut package = participants[0].public_key_package().unwrap();
+    let parent = package.public_key;
+    package.tweak_public_key(tweak);
+    assert!(new_participants::<G>(2, 3)[0].tweak_add(tweak).is_err());
+
+    for p in participants.iter_mut() {
+        p.tweak_add(tweak).unwrap();
+    }
+    assert_eq!(package.public_key, parent + G::GENERATOR * tweak);
+    assert_eq!(
+        participants[0]
+            .public_key_package()
+            .unwrap()
+            .verification_shares,
+        package.verification_shares
+    );
+    let shares = participants
+        .iter()
+        .map(|p| {
+            assert_eq!(p.get_public_key().unwrap(), package.public_key);
+            assert_eq!(
+                G::GENERATOR * p.get_secret_share().unwrap(),
+                package.verification_shares[&p.get_id()]
+            );
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares[1..]).unwrap();
+    assert_eq!(G::GENERATOR * secret, package.public_key);
+}
+
+#[test]
+fn aggregate_dkgs() {
+    type G = k256::ProjectivePoint;
+    let mut first = run_dkg::<G>(2, 3);
+    let second = run_dkg::<G>(2, 3);
+    let public_key = aggregate_public_keys(&[
+        first[0].get_public_key().unwrap(),
+        second[0].get_public_key().unwrap(),
+    ]);
+
+    let commitments = second[0].get_public_commitments().to_vec();
+    assert!(first[0]
+        .add_share_from(second[1].get_secret_share().unwrap(), &commitments)
+        .is_err());
+    assert!(first[0]
+        .add_share_from(second[0].get_secret_share().unwrap(), &commitments[1..])
+        .is_err());
+    for (p, q) in first.iter_mut().zip(second.iter()) {
+        p.add_share_from(q.get_secret_share().unwrap(), &commitments)
+            .unwrap();
+        assert_eq!(p.get_public_key().unwrap(), public_key);
+    }
+
+    let package = first[0].public_key_package().unwrap();
+    let shares = first
+        .iter()
+        .map(|p| {
+            assert_eq!(
+                G::GENERATOR * p.get_secret_share().unwrap(),
+                package.verification_shares[&p.get_id()]
+            );
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares[..2]).unwrap();
+    assert_eq!(G::GENERATOR * secret, public_key);
+}
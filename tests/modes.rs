@@ -0,0 +1,656 @@
+mod common;
+
+use common::*;
+use gennaro_dkg::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::num::NonZeroUsize;
+use vsss_rs::{
+    combine_shares,
+    elliptic_curve::{group::GroupEncoding, Group},
+    Share,
+};
+
+#[test]
+fn from_polynomials() {
+    type G = k256::ProjectivePoint;
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+    let scalar = |i: u64| k256::Scalar::from(i);
+    let mut participants = (1..=3u64)
+        .map(|i| {
+            SecretParticipant::<G>::from_polynomials(
+                NonZeroUsize::new(i as usize).unwrap(),
+                parameters,
+                vec![scalar(i), scalar(i + 10)],
+                vec![scalar(i + 20), scalar(i + 30)],
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(SecretParticipant::<G>::from_polynomials(
+        NonZeroUsize::new(1).unwrap(),
+        parameters,
+        vec![scalar(1)],
+        vec![scalar(2)],
+    )
+    .is_err());
+
+    complete_dkg(&mut participants);
+
+    // f(x) = (1 + 2 + 3) + (11 + 12 + 13)x
+    for p in &participants {
+        assert_eq!(p.get_public_key(), Some(G::GENERATOR * scalar(6)));
+        let x = p.get_id() as u64;
+        assert_eq!(p.get_secret_share(), Some(scalar(6 + 36 * x)));
+    }
+}
+
+#[test]
+fn known_answer_k256() {
+    // Participant i holds f(i) of the combined polynomial f, its id is the
+    // evaluation point. The dealers' polynomials are
+    //   f_1(x) = 3 + 5x, f_2(x) = 7 + 11x, f_3(x) = 13 + 17x
+    // so f(x) = 23 + 33x, the secret is 23 and the shares are
+    //   f(1) = 56, f(2) = 89, f(3) = 122
+    // The points were computed with an independent secp256k1 implementation.
+    type G = k256::ProjectivePoint;
+    let scalar = |i: u64| k256::Scalar::from(i);
+    let point = |hex: &str| {
+        let bytes = data_encoding::HEXLOWER.decode(hex.as_bytes()).unwrap();
+        G::from_bytes(k256::CompressedPoint::from_slice(&bytes)).unwrap()
+    };
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+    let polynomials = [(3, 5, 2, 4), (7, 11, 6, 8), (13, 17, 10, 12)];
+    let mut participants = polynomials
+        .iter()
+        .enumerate()
+        .map(|(i, (a, b, c, d))| {
+            SecretParticipant::<G>::from_polynomials(
+                NonZeroUsize::new(i + 1).unwrap(),
+                parameters,
+                vec![scalar(*a), scalar(*b)],
+                vec![scalar(*c), scalar(*d)],
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    complete_dkg(&mut participants);
+
+    let public_key = point("032fa2104d6b38d11b0230010559879124e42ab8dfeff5ff29dc9cdadd4ecacc3f");
+    let linear = point("021697ffa6fd9de627c077e3d2fe541084ce13300b0bec1146f95ae57f0d0bd6a5");
+    let verification_shares = [
+        point("02bce74de6d5f98dc027740c2bbff05b6aafe5fd8d103f827e48894a2bd3460117"),
+        point("02d3cc30ad6b483e4bc79ce2c9dd8bc54993e947eb8df787b442943d3f7b527eaf"),
+        point("02139ae46a1133f1f9d23f25efba0f6dd87bf7ddaf568a5fb9e0a3bfda73176237"),
+    ];
+    let shares = [56, 89, 122];
+
+    for (i, p) in participants.iter().enumerate() {
+        assert_eq!(p.get_public_key(), Some(public_key));
+        assert_eq!(p.get_public_commitments(), &[public_key, linear]);
+        assert_eq!(p.get_secret_share(), Some(scalar(shares[i])));
+        let package = p.export().unwrap();
+        for (j, share) in verification_shares.iter().enumerate() {
+            assert_eq!(package.verification_shares[&(j + 1)], *share);
+        }
+    }
+}
+
+#[test]
+fn in_memory_secret_store() {
+    let parameters = Parameters::<k256::ProjectivePoint>::new(
+        NonZeroUsize::new(2).unwrap(),
+        NonZeroUsize::new(3).unwrap(),
+    );
+    let mut participants = (1..=3)
+        .map(|id| {
+            SecretParticipant::<k256::ProjectivePoint, InMemoryStore>::new(
+                NonZeroUsize::new(id).unwrap(),
+                parameters,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    complete_dkg(&mut participants);
+
+    let shares = participants
+        .iter()
+        .map(|p| {
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares).unwrap();
+    assert_eq!(
+        participants[0].get_public_key().unwrap(),
+        k256::ProjectivePoint::GENERATOR * secret
+    );
+
+    let json = serde_json::to_string(&participants[0]).unwrap();
+    let p0 = serde_json::from_str::<SecretParticipant<k256::ProjectivePoint, InMemoryStore>>(&json)
+        .unwrap();
+    assert_eq!(p0.get_secret_share(), participants[0].get_secret_share());
+}
+
+#[test]
+fn restart_with() {
+    type G = k256::ProjectivePoint;
+    let participants = new_participants::<G>(2, 4);
+    let valid_ids = maplit::btreeset! { 1, 3, 4 };
+
+    assert!(participants[1].restart_with(valid_ids.clone()).is_err());
+    assert!(participants[0]
+        .restart_with(maplit::btreeset! { 1 })
+        .is_err());
+
+    let mut restarted = valid_ids
+        .iter()
+        .map(|id| {
+            participants[id - 1]
+                .restart_with(valid_ids.clone())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    for (i, p) in restarted.iter().enumerate() {
+        assert_eq!(p.get_id(), i + 1);
+        assert_eq!(p.get_limit(), 3);
+        assert_eq!(p.get_threshold(), 2);
+        assert_eq!(p.get_round(), Round::One);
+    }
+    complete_dkg(&mut restarted);
+    let pk = restarted[0].get_public_key().unwrap();
+    assert!(restarted.iter().all(|p| p.get_public_key() == Some(pk)));
+}
+
+#[test]
+fn enrollment() {
+    type G = k256::ProjectivePoint;
+    const NEW_ID: usize = 4;
+    let mut participants = new_participants::<G>(2, 3);
+    complete_dkg(&mut participants);
+    let quorum = maplit::btreeset! { 1, 3 };
+
+    assert!(participants[1].enrollment_masks(NEW_ID, &quorum).is_err());
+    assert!(participants[0].enrollment_masks(3, &quorum).is_err());
+
+    // Each quorum member sends one mask to every member
+    let mut received = BTreeMap::<usize, BTreeMap<usize, k256::Scalar>>::new();
+    for id in &quorum {
+        let masks = participants[id - 1]
+            .enrollment_masks(NEW_ID, &quorum)
+            .unwrap();
+        assert_eq!(masks.keys().copied().collect::<BTreeSet<_>>(), quorum);
+        for (to, mask) in masks {
+            received.entry(to).or_default().insert(*id, mask);
+        }
+    }
+    let contributions = quorum
+        .iter()
+        .map(|id| {
+            let share = participants[id - 1]
+                .enrollment_share_for(NEW_ID, &received[id])
+                .unwrap();
+            (*id, share)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+    let commitments = participants[0].get_public_commitments();
+    let enrolled = SecretParticipant::<G>::from_enrollment(
+        NonZeroUsize::new(NEW_ID).unwrap(),
+        parameters,
+        commitments,
+        &contributions,
+    )
+    .unwrap();
+    assert!(enrolled.completed());
+    assert_eq!(enrolled.get_public_key(), participants[0].get_public_key());
+
+    let shares = [&participants[1], &enrolled]
+        .iter()
+        .map(|p| {
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares).unwrap();
+    assert_eq!(enrolled.get_public_key(), Some(G::GENERATOR * secret));
+
+    let mut wrong = contributions.clone();
+    *wrong.get_mut(&1).unwrap() += k256::Scalar::ONE;
+    assert!(SecretParticipant::<G>::from_enrollment(
+        NonZeroUsize::new(NEW_ID).unwrap(),
+        parameters,
+        commitments,
+        &wrong,
+    )
+    .is_err());
+}
+
+#[test]
+fn participant_registry() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 4);
+    let parameters = participants[0].get_parameters();
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    let mut registry = ParticipantRegistry::new(parameters);
+    registry.register(1, r1bdata[&1].clone()).unwrap();
+    assert!(registry.freeze().is_err());
+    assert!(registry.round2_inputs(1, BTreeMap::new()).is_err());
+    registry.register(2, r1bdata[&2].clone()).unwrap();
+    registry.register(3, r1bdata[&3].clone()).unwrap();
+    assert!(registry.register(3, r1bdata[&3].clone()).is_err());
+    assert!(registry.register(5, r1bdata[&3].clone()).is_err());
+    assert_eq!(registry.freeze().unwrap(), maplit::btreeset! {1, 2, 3});
+
+    // Participant 4 missed the window
+    assert!(registry.register(4, r1bdata[&4].clone()).is_err());
+    assert!(registry.round2_inputs(4, BTreeMap::new()).is_err());
+
+    for p in participants.iter_mut().take(3) {
+        let id = p.get_id();
+        let p2p = r1p2pdata
+            .iter()
+            .filter(|(from, _)| **from != id)
+            .map(|(from, p2p)| (*from, p2p[&id].clone()))
+            .collect();
+        let (bdata, p2p) = registry.round2_inputs(id, p2p).unwrap();
+        assert!(!bdata.contains_key(&4));
+        assert!(!p2p.contains_key(&4));
+        let echo = p.round2(bdata, p2p).unwrap();
+        assert_eq!(echo.valid_participant_ids(), &registry.members());
+    }
+}
+
+#[test]
+fn coordinated_mode() {
+    use std::sync::{Arc, Mutex};
+    type G = k256::ProjectivePoint;
+    type Scalar = <G as Group>::Scalar;
+    let coordinator_key = Scalar::from(42u64);
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap())
+            .with_coordinator(G::GENERATOR * coordinator_key);
+    assert_eq!(
+        parameters.coordinator(),
+        Some(G::GENERATOR * coordinator_key)
+    );
+    let mut participants = (1..=3)
+        .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect::<Vec<_>>();
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        p.round2(bdata, p2p).unwrap();
+    }
+
+    let echo = Round2EchoBroadcastData::new(maplit::btreeset! {1, 2, 3});
+    let forged = SignedValidSet::<G>::sign(
+        &parameters,
+        echo.clone(),
+        &Scalar::from(7u64),
+        rand_core::OsRng,
+    );
+    assert!(participants[0].round3_coordinated(&forged).is_err());
+    // A set signed for another session is not accepted
+    let other_session =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap())
+            .with_coordinator(G::GENERATOR * coordinator_key);
+    let replayed = SignedValidSet::<G>::sign(
+        &other_session,
+        echo.clone(),
+        &coordinator_key,
+        rand_core::OsRng,
+    );
+    assert!(!replayed.verify(&parameters, G::GENERATOR * coordinator_key));
+    assert!(participants[0].round3_coordinated(&replayed).is_err());
+    assert_eq!(participants[0].get_round(), Round::Three);
+
+    let signed = SignedValidSet::<G>::sign(&parameters, echo, &coordinator_key, rand_core::OsRng);
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3_coordinated(&signed).unwrap());
+    }
+    let mut public_key = G::IDENTITY;
+    for p in participants.iter_mut() {
+        public_key = p.round4(&r3bdata).unwrap().public_key;
+    }
+
+    let sign = |parameters: &Parameters<G>, public_key: G, secret_key: &Scalar| {
+        SignedPublicKey::sign(parameters, public_key, secret_key, rand_core::OsRng)
+    };
+    let forged = sign(&parameters, public_key, &Scalar::from(7u64));
+    let wrong_key = sign(&parameters, G::GENERATOR, &coordinator_key);
+    let replayed = sign(&other_session, public_key, &coordinator_key);
+    let signed = sign(&parameters, public_key, &coordinator_key);
+    for p in &participants {
+        assert!(p.round5_coordinated(&forged).is_err());
+        assert!(p.round5_coordinated(&wrong_key).is_err());
+        assert!(p.round5_coordinated(&replayed).is_err());
+        p.round5_coordinated(&signed).unwrap();
+    }
+
+    // Without a coordinator the coordinated rounds are unavailable
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let (bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    participants[0].round2(bdata, p2p).unwrap();
+    let signed = SignedValidSet::<G>::sign(
+        &participants[0].get_parameters(),
+        Round2EchoBroadcastData::new(maplit::btreeset! {1, 2, 3}),
+        &coordinator_key,
+        rand_core::OsRng,
+    );
+    assert!(participants[0].round3_coordinated(&signed).is_err());
+
+    // The fault policy and drop observer apply like in round 3
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap())
+            .with_coordinator(G::GENERATOR * coordinator_key)
+            .with_max_faults(1);
+    let mut participants = (1..=4)
+        .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect::<Vec<_>>();
+    let dropped = Arc::new(Mutex::new(Vec::new()));
+    let observed = dropped.clone();
+    participants[0].set_on_drop(move |id| observed.lock().unwrap().push(id));
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let (bdata, p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    participants[0].round2(bdata, p2p).unwrap();
+
+    let sign_set = |ids: BTreeSet<usize>| {
+        SignedValidSet::<G>::sign(
+            &parameters,
+            Round2EchoBroadcastData::new(ids),
+            &coordinator_key,
+            rand_core::OsRng,
+        )
+    };
+    assert!(matches!(
+        participants[0].round3_coordinated(&sign_set(maplit::btreeset! {1, 2})),
+        Err(Error::Abort(AbortReason::TooManyFaults))
+    ));
+    assert!(dropped.lock().unwrap().is_empty());
+    participants[0]
+        .round3_coordinated(&sign_set(maplit::btreeset! {1, 2, 3}))
+        .unwrap();
+    assert_eq!(*dropped.lock().unwrap(), vec![4]);
+}
+
+#[test]
+fn authenticated_rounds() {
+    type G = k256::ProjectivePoint;
+    type Scalar = <G as Group>::Scalar;
+    let signing_keys = (1..=3)
+        .map(|id| (id, Scalar::from(id as u64 + 1000)))
+        .collect::<BTreeMap<_, _>>();
+    let verifying_keys = signing_keys
+        .iter()
+        .map(|(id, key)| (*id, G::GENERATOR * key))
+        .collect::<BTreeMap<_, _>>();
+    let mut participants = new_participants::<G>(2, 3);
+    assert!(participants[0]
+        .round1_authenticated(rand_core::OsRng)
+        .is_err());
+    assert!(participants[0]
+        .set_authentication_keys(&signing_keys[&2], verifying_keys.clone())
+        .is_err());
+
+    let mut r1bdata = BTreeMap::new();
+    let mut r1p2pdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let id = p.get_id();
+        p.set_authentication_keys(&signing_keys[&id], verifying_keys.clone())
+            .unwrap();
+        let (bdata, p2p) = p.round1_authenticated(rand_core::OsRng).unwrap();
+        r1bdata.insert(id, bdata);
+        r1p2pdata.insert(id, p2p);
+    }
+    let inputs = |id: usize| {
+        let bdata = r1bdata
+            .iter()
+            .filter(|(i, _)| **i != id)
+            .map(|(i, b)| (*i, b.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let p2p = r1p2pdata
+            .iter()
+            .filter(|(i, _)| **i != id)
+            .map(|(i, p)| (*i, p[&id].clone()))
+            .collect::<BTreeMap<_, _>>();
+        (bdata, p2p)
+    };
+
+    // Data addressed to another participant is rejected
+    let (bdata, mut p2p) = inputs(1);
+    p2p.insert(2, r1p2pdata[&2][&3].clone());
+    assert!(matches!(
+        participants[0].round2_authenticated(bdata, p2p),
+        Err(Error::BadSignature { from: 2 })
+    ));
+    // A forged broadcast is rejected
+    let (mut bdata, p2p) = inputs(1);
+    bdata.get_mut(&3).unwrap().data = r1bdata[&2].data.clone();
+    assert!(matches!(
+        participants[0].round2_authenticated(bdata, p2p),
+        Err(Error::BadSignature { from: 3 })
+    ));
+    assert_eq!(participants[0].get_round(), Round::Two);
+
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = inputs(p.get_id());
+        p.round2_authenticated(bdata, p2p).unwrap();
+    }
+}
+
+#[cfg(feature = "encrypted-shares")]
+#[test]
+fn encrypted_shares() {
+    type G = k256::ProjectivePoint;
+    type Scalar = <G as Group>::Scalar;
+    let mut participants = new_participants::<G>(2, 3);
+    let decryption_keys = (1..=3)
+        .map(|id| (id, Scalar::from(id as u64 + 100)))
+        .collect::<BTreeMap<_, _>>();
+    let encryption_keys = decryption_keys
+        .iter()
+        .map(|(id, sk)| (*id, G::GENERATOR * sk))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut partial = encryption_keys.clone();
+    partial.remove(&3);
+    assert!(participants[0]
+        .round1_encrypted(&partial, rand_core::OsRng)
+        .is_err());
+    assert_eq!(participants[0].get_round(), Round::One);
+
+    let mut r1bdata = BTreeMap::new();
+    let mut r1p2pdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = p
+            .round1_encrypted(&encryption_keys, rand_core::OsRng)
+            .unwrap();
+        r1bdata.insert(p.get_id(), bdata);
+        r1p2pdata.insert(p.get_id(), p2p);
+    }
+    assert!(!r1p2pdata[&1][&2].ciphertext.is_empty());
+
+    // A share encrypted to the wrong key is dropped like an invalid share
+    let mut wrong = r1p2pdata[&1][&3].clone();
+    wrong.ephemeral_key = G::GENERATOR;
+    let mut bdata = r1bdata.clone();
+    bdata.remove(&3);
+    let p2p = maplit::btreemap! { 1 => wrong, 2 => r1p2pdata[&2][&3].clone() };
+    let mut third = participants[2].clone();
+    third
+        .round2_encrypted(&decryption_keys[&3], bdata, p2p)
+        .unwrap();
+    assert_eq!(third.valid_participant_count(), 2);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let id = p.get_id();
+        let mut bdata = r1bdata.clone();
+        bdata.remove(&id);
+        let p2p = r1p2pdata
+            .iter()
+            .filter(|(sender, _)| **sender != id)
+            .map(|(sender, p2p)| (*sender, p2p[&id].clone()))
+            .collect();
+        let echo = p
+            .round2_encrypted(&decryption_keys[&id], bdata, p2p)
+            .unwrap();
+        r2bdata.insert(id, echo);
+    }
+    assert!(r2bdata
+        .values()
+        .all(|e| e.valid_participant_ids().len() == 3));
+}
+
+#[cfg(feature = "encrypted-storage")]
+#[test]
+fn encrypted_storage() {
+    use vsss_rs::elliptic_curve::PrimeField;
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    complete_dkg(&mut participants);
+    let key = [7u8; 32];
+
+    let bytes = participants[0].to_encrypted_bytes(&key).unwrap();
+    let share = participants[0].get_secret_share().unwrap();
+    assert!(!bytes.windows(32).any(|w| w == share.to_repr().as_slice()));
+
+    let restored = SecretParticipant::<G>::from_encrypted_bytes(&key, &bytes).unwrap();
+    assert_eq!(restored.get_secret_share(), Some(share));
+    assert_eq!(restored.get_public_key(), participants[0].get_public_key());
+
+    assert!(SecretParticipant::<G>::from_encrypted_bytes(&[8u8; 32], &bytes).is_err());
+    let mut tampered = bytes.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(SecretParticipant::<G>::from_encrypted_bytes(&key, &tampered).is_err());
+    assert!(SecretParticipant::<G>::from_encrypted_bytes(&key, &bytes[..10]).is_err());
+}
+
+#[test]
+fn verifier_only_participant() {
+    type G = k256::ProjectivePoint;
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+    assert!(
+        SecretParticipant::<G>::new_verifier_only(NonZeroUsize::new(3).unwrap(), parameters)
+            .is_err()
+    );
+    let mut verifier =
+        SecretParticipant::<G>::new_verifier_only(NonZeroUsize::new(4).unwrap(), parameters)
+            .unwrap();
+    assert!(verifier.is_verifier_only());
+    assert!(verifier.round1().is_err());
+
+    let mut participants = new_participants::<G>(2, 3);
+    let (bdata, p2p) = run_round1(&mut participants);
+    let mut echoes = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bmap, p2pmap) = round2_inputs(p.get_id(), &bdata, &p2p);
+        echoes.insert(p.get_id(), p.round2(bmap, p2pmap).unwrap());
+    }
+    let verifier_echo = verifier.round2(bdata.clone(), BTreeMap::new()).unwrap();
+    assert_eq!(
+        verifier_echo.valid_participant_ids(),
+        &maplit::btreeset! {1, 2, 3}
+    );
+
+    verifier.round3(&echoes).unwrap();
+    // The other participants ignore the verifier's echo
+    echoes.insert(4, verifier_echo);
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&echoes).unwrap());
+    }
+
+    let mut r4bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+    }
+    let verifier_r4 = verifier.round4(&r3bdata).unwrap();
+    assert_eq!(verifier_r4.public_key, r4bdata[&1].public_key);
+    assert_eq!(
+        verifier.get_public_commitments(),
+        participants[0].get_public_commitments()
+    );
+    verifier.round5(&r4bdata).unwrap();
+    for p in &participants {
+        p.round5(&r4bdata).unwrap();
+    }
+
+    assert!(verifier.get_secret_share().is_none());
+    assert!(matches!(
+        verifier.try_get_secret_share(),
+        Err(Error::VerifierOnly)
+    ));
+    assert_eq!(verifier.get_public_key(), participants[0].get_public_key());
+
+    let mut tampered = r4bdata.clone();
+    tampered.get_mut(&2).unwrap().public_key = G::GENERATOR;
+    assert!(verifier.round5(&tampered).is_err());
+}
+
+#[test]
+fn ceremony_manifest() {
+    type G = k256::ProjectivePoint;
+    let participants = run_dkg::<G>(2, 3);
+    let manifest = participants[1].manifest().unwrap();
+    assert_eq!(manifest.signer, 2);
+    assert_eq!(manifest.participant_ids, maplit::btreeset! {1, 2, 3});
+    assert!(manifest.dropped_ids.is_empty());
+    assert_eq!(
+        manifest.public_key,
+        participants[0].get_public_key().unwrap()
+    );
+    manifest.verify_manifest().unwrap();
+    assert_eq!(
+        manifest.manifest_hash,
+        participants[0].manifest().unwrap().manifest_hash
+    );
+
+    let bytes = serde_bare::to_vec(&manifest).unwrap();
+    let restored = serde_bare::from_slice::<CeremonyManifest<G>>(&bytes).unwrap();
+    restored.verify_manifest().unwrap();
+
+    let mut tampered = manifest.clone();
+    tampered.dropped_ids.insert(3);
+    tampered.participant_ids.remove(&3);
+    assert!(tampered.verify_manifest().is_err());
+
+    let mut tampered = manifest.clone();
+    tampered.signer = 1;
+    assert!(matches!(
+        tampered.verify_manifest(),
+        Err(Error::BadSignature { from: 1 })
+    ));
+
+    let mut tampered = manifest;
+    tampered.parameters_fingerprint[0] ^= 1;
+    assert!(tampered.verify_manifest().is_err());
+
+    let incomplete = new_participants::<G>(2, 3);
+    assert!(matches!(incomplete[0].manifest(), Err(Error::NotComplete)));
+
+    // A manifest made with another hasher only verifies with that hasher
+    struct XorHasher;
+    impl DkgHasher for XorHasher {
+        fn digest(inputs: &[&[u8]]) -> [u8; 32] {
+            let mut digest = Sha256Hasher::digest(inputs);
+            digest[0] ^= 1;
+            digest
+        }
+    }
+    let manifest = participants[1].manifest_with::<XorHasher>().unwrap();
+    manifest.verify_manifest_with::<XorHasher>().unwrap();
+    assert_eq!(
+        manifest.parameters_fingerprint,
+        manifest.parameters.fingerprint_with::<XorHasher>()
+    );
+    assert!(manifest.verify_manifest().is_err());
+}
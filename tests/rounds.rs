@@ -0,0 +1,839 @@
+mod common;
+
+use common::*;
+use gennaro_dkg::*;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use vsss_rs::{
+    combine_shares,
+    elliptic_curve::{group::GroupEncoding, Group},
+    Share,
+};
+
+#[test]
+fn round_order() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    let _ = run_round1(&mut participants);
+    let res = participants[0].round3(&BTreeMap::new());
+    assert!(matches!(
+        res,
+        Err(Error::RoundOrder {
+            expected: 3,
+            got: 2
+        })
+    ));
+    let res = participants[0].round1();
+    assert!(matches!(
+        res,
+        Err(Error::RoundOrder {
+            expected: 1,
+            got: 2
+        })
+    ));
+    let res = participants[0].round5(&BTreeMap::new());
+    assert!(matches!(
+        res,
+        Err(Error::RoundOrder {
+            expected: 5,
+            got: 2
+        })
+    ));
+}
+
+#[test]
+fn round_progress() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    assert_eq!(participants[0].round_name(), "Round 1: share polynomials");
+    assert_eq!(participants[0].rounds_remaining(), 5);
+    run_round1(&mut participants);
+    assert_eq!(participants[0].round_name(), "Round 2: verify shares");
+    assert_eq!(participants[0].rounds_remaining(), 4);
+
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    complete_dkg(&mut participants);
+    assert_eq!(participants[0].round_name(), "Round 5: confirm public key");
+    assert_eq!(participants[0].rounds_remaining(), 1);
+}
+
+#[test]
+fn public_key_so_far() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    assert_eq!(
+        participants[0].public_key_so_far(),
+        k256::ProjectivePoint::IDENTITY
+    );
+    complete_dkg(&mut participants);
+    for p in &participants {
+        assert_eq!(Some(p.public_key_so_far()), p.get_public_key());
+    }
+}
+
+#[test]
+fn try_getters() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    assert!(matches!(
+        participants[0].try_get_secret_share(),
+        Err(Error::NotComplete)
+    ));
+    assert!(matches!(
+        participants[0].try_get_public_key(),
+        Err(Error::NotComplete)
+    ));
+    assert!(matches!(participants[0].export(), Err(Error::NotComplete)));
+
+    complete_dkg(&mut participants);
+    assert_eq!(
+        participants[0].try_get_secret_share().unwrap(),
+        participants[0].get_secret_share().unwrap()
+    );
+    assert_eq!(
+        participants[0].try_get_public_key().unwrap(),
+        participants[0].get_public_key().unwrap()
+    );
+}
+
+#[test]
+fn valid_participant_iterator() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    assert_eq!(participants[0].valid_participant_count(), 0);
+    assert!(!participants[0].is_valid_participant(2));
+    complete_dkg(&mut participants);
+    assert_eq!(
+        participants[0].valid_participant_ids().collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(participants[0].valid_participant_count(), 3);
+    assert!(participants[0].is_valid_participant(2));
+    assert!(!participants[0].is_valid_participant(4));
+}
+
+#[test]
+fn id_exceeds_limit() {
+    type G = k256::ProjectivePoint;
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+    assert!(matches!(
+        SecretParticipant::<G>::new(NonZeroUsize::new(5).unwrap(), parameters),
+        Err(Error::InitializationError(_))
+    ));
+    assert!(matches!(
+        RefreshParticipant::<G>::new(NonZeroUsize::new(4).unwrap(), parameters),
+        Err(Error::InitializationError(_))
+    ));
+    assert!(SecretParticipant::<G>::new(NonZeroUsize::new(3).unwrap(), parameters).is_ok());
+}
+
+#[test]
+fn single_participant_threshold() {
+    type G = k256::ProjectivePoint;
+    let one = NonZeroUsize::new(1).unwrap();
+    for limit in [1, 3] {
+        let parameters = Parameters::<G>::new(one, NonZeroUsize::new(limit).unwrap());
+        assert!(matches!(
+            SecretParticipant::<G>::new(one, parameters),
+            Err(Error::InitializationError(_))
+        ));
+        assert!(matches!(
+            RefreshParticipant::<G>::new(one, parameters),
+            Err(Error::InitializationError(_))
+        ));
+    }
+}
+
+#[test]
+fn message_sizes() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let sizes = participants[0].message_sizes().unwrap();
+    let (bdata, p2p) = participants[0].round1().unwrap();
+    assert_eq!(sizes.round, Round::One);
+    assert_eq!(sizes.broadcast, bdata.to_bytes().unwrap().len());
+    assert_eq!(
+        sizes.p2p,
+        p2p.values()
+            .map(|d| d.to_bytes().unwrap().len())
+            .sum::<usize>()
+    );
+
+    let mut participants = run_dkg::<G>(2, 3);
+    let sizes = participants[0].message_sizes().unwrap();
+    assert_eq!(sizes.round, Round::Five);
+    assert_eq!((sizes.broadcast, sizes.p2p), (0, 0));
+    participants[0].reset().unwrap();
+    assert_eq!(participants[0].message_sizes().unwrap().round, Round::One);
+}
+
+#[test]
+fn round1_outgoing_messages() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let messages: Round1Messages<G> = participants[0].round1_output().unwrap();
+    let outgoing = messages.outgoing().collect::<Vec<_>>();
+    assert_eq!(outgoing.len(), 3);
+    assert!(matches!(
+        &outgoing[0],
+        (Recipient::All, MessagePayload::Round1Broadcast(b)) if *b == messages.broadcast
+    ));
+    for (recipient, (id, p2p)) in outgoing[1..].iter().zip(messages.p2p.iter()) {
+        assert!(matches!(
+            recipient,
+            (Recipient::One(to), MessagePayload::Round1P2P(data)) if to == id && data == p2p
+        ));
+    }
+    assert_eq!(
+        outgoing[1..]
+            .iter()
+            .map(|(recipient, _)| *recipient)
+            .collect::<Vec<_>>(),
+        vec![Recipient::One(2), Recipient::One(3)]
+    );
+}
+
+#[test]
+fn round2_with_preloaded_p2p() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    let mut preloaded = participants.clone();
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    // The p2p data arrives before round 1 broadcasts are available
+    for p in preloaded.iter_mut() {
+        for (from, data) in &r1p2pdata {
+            if let Some(d) = data.get(&p.get_id()) {
+                p.preload_p2p(*from, d.clone()).unwrap();
+            }
+        }
+        let own = r1p2pdata[&1].values().next().unwrap().clone();
+        assert!(p.preload_p2p(p.get_id(), own).is_err());
+        p.round1().unwrap();
+    }
+
+    let mut r2bdata = BTreeMap::new();
+    for (p, q) in participants.iter_mut().zip(preloaded.iter_mut()) {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        let expected = p.round2(bdata.clone(), p2p).unwrap();
+        let echo = q.round2_with_preloaded(bdata).unwrap();
+        assert_eq!(
+            expected.valid_participant_ids(),
+            echo.valid_participant_ids()
+        );
+        let late = r1p2pdata[&1].values().next().unwrap().clone();
+        assert!(q.preload_p2p(1, late).is_err());
+        r2bdata.insert(p.get_id(), echo);
+    }
+
+    // Both groups finish with the same shares
+    for group in [&mut participants, &mut preloaded] {
+        let mut r3bdata = BTreeMap::new();
+        for p in group.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        for p in group.iter_mut() {
+            p.round4(&r3bdata).unwrap();
+        }
+    }
+    for (p, q) in participants.iter().zip(preloaded.iter()) {
+        assert!(p.get_secret_share().is_some());
+        assert_eq!(p.get_secret_share(), q.get_secret_share());
+    }
+}
+
+#[test]
+fn round2_mismatched_channels() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 4);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    assert!(participants[0].missing_round1_data().is_empty());
+
+    // Senders over only one channel are excluded and reported
+    let (mut bdata, mut p2p) = round2_inputs(1, &r1bdata, &r1p2pdata);
+    p2p.remove(&3);
+    bdata.remove(&4);
+    let echo = participants[0].round2(bdata, p2p).unwrap();
+    assert_eq!(echo.valid_participant_ids(), &maplit::btreeset! {1, 2});
+    let missing = participants[0].missing_round1_data();
+    assert_eq!(missing.len(), 2);
+    assert!(matches!(missing[0], Error::MissingP2PData { from: 3 }));
+    assert!(matches!(
+        missing[1],
+        Error::MissingBroadcastData { from: 4 }
+    ));
+
+    let (bdata, p2p) = round2_inputs(2, &r1bdata, &r1p2pdata);
+    participants[1].round2(bdata, p2p).unwrap();
+    assert!(participants[1].missing_round1_data().is_empty());
+}
+
+#[test]
+fn round2_rejects_copied_commitments() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 4);
+    let (mut bdata, p2p) = run_round1(&mut participants);
+    let copied = bdata[&2].clone();
+    bdata.insert(4, copied);
+
+    let (bmap, p2pmap) = round2_inputs(1, &bdata, &p2p);
+    assert!(matches!(
+        participants[0].round2(bmap, p2pmap),
+        Err(Error::DuplicateCommitments { ids }) if ids == maplit::btreeset! {2, 4}
+    ));
+
+    // Copying this participant's own commitments is detected as well
+    let own = bdata[&1].clone();
+    bdata.insert(4, own);
+    let (bmap, p2pmap) = round2_inputs(1, &bdata, &p2p);
+    assert!(matches!(
+        participants[0].round2(bmap, p2pmap),
+        Err(Error::DuplicateCommitments { ids }) if ids == maplit::btreeset! {1, 4}
+    ));
+    assert_eq!(participants[0].get_round(), Round::Two);
+}
+
+#[test]
+fn missing_contributors() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    assert_eq!(
+        participants[0].missing_contributors(),
+        maplit::btreeset! {2, 3}
+    );
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    participants[0]
+        .preload_p2p(2, r1p2pdata[&2][&1].clone())
+        .unwrap();
+    assert_eq!(
+        participants[0].missing_contributors(),
+        maplit::btreeset! {3}
+    );
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    assert!(participants[0].missing_contributors().is_empty());
+
+    // Participant 3 is slow to echo
+    r2bdata.remove(&3);
+    participants[0].round3(&r2bdata).unwrap();
+    assert_eq!(
+        participants[0].missing_contributors(),
+        maplit::btreeset! {3}
+    );
+}
+
+#[test]
+fn set_valid_participants() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 4);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+
+    // The coordinator decided to exclude participant 4
+    let ids = maplit::btreeset! { 1, 2, 3, 5 };
+    assert!(participants[0]
+        .set_valid_participants(maplit::btreeset! { 2, 3 })
+        .is_err());
+    assert!(participants[0]
+        .set_valid_participants(maplit::btreeset! { 1 })
+        .is_err());
+    for p in participants.iter_mut().take(3) {
+        p.set_valid_participants(ids.clone()).unwrap();
+        assert_eq!(
+            p.get_valid_participant_ids(),
+            &maplit::btreeset! { 1, 2, 3 }
+        );
+    }
+
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut().take(3) {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    let mut r4bdata = BTreeMap::new();
+    let mut shares = Vec::new();
+    for p in participants.iter_mut().take(3) {
+        r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        shares.push(
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap(),
+        );
+    }
+    for p in participants.iter().take(3) {
+        p.round5(&r4bdata).unwrap();
+    }
+
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares).unwrap();
+    assert_eq!(
+        r4bdata[&1].public_key,
+        k256::ProjectivePoint::GENERATOR * secret
+    );
+}
+
+#[test]
+fn round3_valid_set_intersection() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 4);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    // Participant 2 received a late complaint about participant 4
+    r2bdata.insert(2, Round2EchoBroadcastData::new(maplit::btreeset! {1, 2, 3}));
+
+    let mut r3bdata = BTreeMap::new();
+    for p in participants[..3].iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        assert_eq!(p.get_valid_participant_ids(), &maplit::btreeset! {1, 2, 3});
+    }
+    // Participant 4 is excluded by the intersection
+    assert!(participants[3].round3(&r2bdata).is_err());
+
+    let mut public_key = None;
+    for p in participants[..3].iter_mut() {
+        let key = p.round4(&r3bdata).unwrap().public_key;
+        assert_eq!(*public_key.get_or_insert(key), key);
+    }
+    let shares = participants[..3]
+        .iter()
+        .map(|p| {
+            <Vec<u8> as Share>::from_field_element(p.get_id() as u8, p.get_secret_share().unwrap())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let secret = combine_shares::<k256::Scalar, u8, Vec<u8>>(&shares[..2]).unwrap();
+    assert_eq!(G::GENERATOR * secret, public_key.unwrap());
+}
+
+#[test]
+fn round3_aborts_after_too_many_faults() {
+    type G = k256::ProjectivePoint;
+    let parameters = ParametersBuilder::<G>::new()
+        .threshold(2)
+        .limit(4)
+        .max_faults(1)
+        .build()
+        .unwrap();
+    assert_eq!(parameters.max_faults(), Some(1));
+    let mut participants = (1..=4)
+        .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect::<Vec<_>>();
+    let (bdata, p2p) = run_round1(&mut participants);
+
+    // Participants 3 and 4 never send anything
+    let mut echoes = BTreeMap::new();
+    for p in participants.iter_mut().take(2) {
+        let (mut bmap, mut p2pmap) = round2_inputs(p.get_id(), &bdata, &p2p);
+        bmap.retain(|id, _| *id < 3);
+        p2pmap.retain(|id, _| *id < 3);
+        echoes.insert(p.get_id(), p.round2(bmap, p2pmap).unwrap());
+    }
+    assert!(matches!(
+        participants[0].round3(&echoes),
+        Err(Error::Abort(AbortReason::TooManyFaults))
+    ));
+    assert_eq!(participants[0].get_round(), Round::Three);
+    assert_eq!(participants[0].get_parameters().max_faults(), Some(1));
+
+    // Without the policy the same faults are tolerated
+    let mut participants = new_participants::<G>(2, 4);
+    let (bdata, p2p) = run_round1(&mut participants);
+    let mut echoes = BTreeMap::new();
+    for p in participants.iter_mut().take(2) {
+        let (mut bmap, mut p2pmap) = round2_inputs(p.get_id(), &bdata, &p2p);
+        bmap.retain(|id, _| *id < 3);
+        p2pmap.retain(|id, _| *id < 3);
+        echoes.insert(p.get_id(), p.round2(bmap, p2pmap).unwrap());
+    }
+    assert!(participants[0].round3(&echoes).is_ok());
+}
+
+#[test]
+fn incremental_round3() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (bdata, p2p) = run_round1(&mut participants);
+    let mut echoes = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bmap, p2pmap) = round2_inputs(p.get_id(), &bdata, &p2p);
+        echoes.insert(p.get_id(), p.round2(bmap, p2pmap).unwrap());
+    }
+    let mut batch = participants[0].clone();
+    let expected = batch.round3(&echoes).unwrap();
+
+    let p = &mut participants[0];
+    p.add_round2_echo(3, echoes[&3].clone()).unwrap();
+    assert!(p.finalize_round3().is_err());
+    assert_eq!(p.get_round(), Round::Three);
+    p.add_round2_echo(2, echoes[&2].clone()).unwrap();
+    p.add_round2_echo(1, echoes[&1].clone()).unwrap();
+    assert_eq!(p.finalize_round3().unwrap(), expected);
+    assert_eq!(p.get_round(), Round::Four);
+    assert_eq!(
+        p.get_valid_participant_ids(),
+        batch.get_valid_participant_ids()
+    );
+    assert!(p.add_round2_echo(2, echoes[&2].clone()).is_err());
+}
+
+#[test]
+fn reconcile_after_partition() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 4);
+    let (bdata, p2p) = run_round1(&mut participants);
+    let mut echoes = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bmap, p2pmap) = round2_inputs(p.get_id(), &bdata, &p2p);
+        echoes.insert(p.get_id(), p.round2(bmap, p2pmap).unwrap());
+    }
+
+    assert_eq!(
+        participants[0].reconcile(&echoes[&2]).unwrap(),
+        ReconcileOutcome::NoChange
+    );
+
+    // The other side of the partition dropped participant 4
+    let peer = Round2EchoBroadcastData::new(maplit::btreeset! {1, 2, 3});
+    assert_eq!(
+        participants[0].reconcile(&peer).unwrap(),
+        ReconcileOutcome::Merged
+    );
+    assert_eq!(
+        participants[0].get_valid_participant_ids(),
+        &maplit::btreeset! {1, 2, 3}
+    );
+    assert_eq!(
+        participants[0].reconcile(&peer).unwrap(),
+        ReconcileOutcome::NoChange
+    );
+
+    // A side that excludes this participant cannot be merged
+    let peer = Round2EchoBroadcastData::new(maplit::btreeset! {2, 3, 4});
+    assert_eq!(
+        participants[0].reconcile(&peer).unwrap(),
+        ReconcileOutcome::AbortRequired
+    );
+    assert_eq!(
+        participants[0].get_valid_participant_ids(),
+        &maplit::btreeset! {1, 2, 3}
+    );
+
+    let mut fresh = new_participants::<G>(2, 4);
+    assert!(fresh[0].reconcile(&peer).is_err());
+}
+
+#[test]
+fn valid_set_approval() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    for p in participants.iter_mut() {
+        p.require_valid_set_approval();
+    }
+    assert!(participants[0].approve_valid_set().is_err());
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    assert!(matches!(
+        participants[0].round3(&r2bdata),
+        Err(Error::AwaitingApproval)
+    ));
+    assert_eq!(participants[0].get_round(), Round::Three);
+
+    // The approval state survives serialization
+    let json = serde_json::to_string(&participants[0]).unwrap();
+    let mut restored =
+        serde_json::from_str::<SecretParticipant<k256::ProjectivePoint>>(&json).unwrap();
+    assert!(matches!(
+        restored.round3(&r2bdata),
+        Err(Error::AwaitingApproval)
+    ));
+    restored.approve_valid_set().unwrap();
+    restored.round3(&r2bdata).unwrap();
+
+    participants[1].approve_valid_set().unwrap();
+    participants[1].round3(&r2bdata).unwrap();
+}
+
+#[test]
+fn diagnostics_snapshot() {
+    type G = k256::ProjectivePoint;
+    const BAD_ID: usize = 3;
+    let mut participants = new_participants::<G>(2, 3);
+    let (bdata, mut p2p) = run_round1(&mut participants);
+    let fingerprint = participants[0].get_parameters().fingerprint();
+    let copied = p2p[&2][&3].clone();
+    p2p.get_mut(&BAD_ID).unwrap().insert(1, copied);
+    let (bmap, p2pmap) = round2_inputs(1, &bdata, &p2p);
+    participants[0].round2(bmap, p2pmap).unwrap();
+
+    let diagnostics = participants[0].diagnostics();
+    assert_eq!(diagnostics.id, 1);
+    assert_eq!(diagnostics.round, Round::Three);
+    assert_eq!(
+        diagnostics.parameters_fingerprint,
+        data_encoding::HEXLOWER.encode(&fingerprint)
+    );
+    assert_eq!(diagnostics.broadcasts_received, 2);
+    assert_eq!(diagnostics.p2p_received, 2);
+    assert_eq!(diagnostics.valid_participant_ids, maplit::btreeset! {1, 2});
+    assert_eq!(diagnostics.dropped_ids, maplit::btreeset! {BAD_ID});
+    assert!(!diagnostics.verifier_only);
+
+    let json = serde_json::to_string(&diagnostics).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Diagnostics>(&json).unwrap(),
+        diagnostics
+    );
+}
+
+#[test]
+fn round4_with_sink() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    let mut sink = MemoryShareSink::<InMemoryStore>::default();
+    assert_eq!(sink.share::<k256::Scalar>(), None);
+    let mut r4bdata = BTreeMap::new();
+    r4bdata.insert(
+        1,
+        participants[0]
+            .round4_with_sink(&r3bdata, &mut sink)
+            .unwrap(),
+    );
+    for p in participants[1..].iter_mut() {
+        r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+    }
+    for p in &participants {
+        p.round5(&r4bdata).unwrap();
+    }
+
+    assert_eq!(participants[0].get_secret_share(), None);
+    assert!(matches!(
+        participants[0].try_get_secret_share(),
+        Err(Error::ShareInSink)
+    ));
+    let share = sink.share::<k256::Scalar>().unwrap();
+    assert_eq!(
+        G::GENERATOR * share,
+        participants[1]
+            .public_key_package()
+            .unwrap()
+            .verification_shares[&1]
+    );
+}
+
+#[test]
+fn round4_output() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    let mut outputs = BTreeMap::new();
+    for p in participants.iter_mut() {
+        outputs.insert(p.get_id(), p.round4_output(&r3bdata).unwrap());
+    }
+    let r4bdata = outputs
+        .iter()
+        .map(|(id, output)| (*id, output.broadcast))
+        .collect::<BTreeMap<_, _>>();
+    for p in &participants {
+        p.round5(&r4bdata).unwrap();
+        let output = &outputs[&p.get_id()];
+        assert_eq!(output.id, p.get_id());
+        assert_eq!(output.broadcast.public_key, p.get_public_key().unwrap());
+        assert_eq!(
+            output.public_commitments,
+            p.get_public_commitments().to_vec()
+        );
+        assert_eq!(
+            output.verification_share,
+            G::GENERATOR * p.get_secret_share().unwrap()
+        );
+        let json = serde_json::to_string(output).unwrap();
+        assert_eq!(
+            &serde_json::from_str::<Round4Output<G>>(&json).unwrap(),
+            output
+        );
+    }
+}
+
+#[test]
+fn degraded_threshold() {
+    type G = k256::ProjectivePoint;
+    type Scalar = <G as Group>::Scalar;
+    let parameters =
+        Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(2).unwrap());
+    // The linear coefficients cancel so the combined polynomial is constant
+    let mut participants = [
+        SecretParticipant::<G>::from_polynomials(
+            NonZeroUsize::new(1).unwrap(),
+            parameters,
+            vec![Scalar::from(3u64), Scalar::from(5u64)],
+            vec![Scalar::from(7u64), Scalar::from(11u64)],
+        )
+        .unwrap(),
+        SecretParticipant::<G>::from_polynomials(
+            NonZeroUsize::new(2).unwrap(),
+            parameters,
+            vec![Scalar::from(13u64), -Scalar::from(5u64)],
+            vec![Scalar::from(17u64), Scalar::from(19u64)],
+        )
+        .unwrap(),
+    ];
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    for p in participants.iter_mut() {
+        assert!(matches!(p.round4(&r3bdata), Err(Error::DegradedThreshold)));
+        assert_eq!(p.get_round(), Round::Four);
+    }
+}
+
+#[test]
+fn subgroup_check() {
+    let mut participants = new_participants::<bls12_381_plus::G1Projective>(2, 3);
+    for p in participants.iter_mut() {
+        p.enable_subgroup_check();
+    }
+    complete_dkg(&mut participants);
+
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+    }
+    participants[0].enable_subgroup_check();
+    assert!(participants[0].round4(&r3bdata).is_ok());
+}
+
+#[test]
+fn public_key_is_order_independent() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(3, 5);
+    let (r1bdata, r1p2pdata) = run_round1(&mut participants);
+    let mut r2bdata = BTreeMap::new();
+    for p in participants.iter_mut() {
+        let (bdata, p2p) = round2_inputs(p.get_id(), &r1bdata, &r1p2pdata);
+        r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+    }
+    let mut r3bdata = Vec::new();
+    for p in participants.iter_mut() {
+        r3bdata.push((p.get_id(), p.round3(&r2bdata).unwrap()));
+    }
+
+    let forward = r3bdata.iter().cloned().collect::<BTreeMap<_, _>>();
+    let reverse = r3bdata.iter().rev().cloned().collect::<BTreeMap<_, _>>();
+    let mut interleaved = BTreeMap::new();
+    for i in [2, 4, 0, 3, 1] {
+        interleaved.insert(r3bdata[i].0, r3bdata[i].1.clone());
+    }
+
+    for p in participants.iter() {
+        let keys = [&forward, &reverse, &interleaved]
+            .iter()
+            .map(|data| {
+                let mut p = p.clone();
+                p.round4(data).unwrap().public_key.to_bytes()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(keys[0], keys[1]);
+        assert_eq!(keys[0], keys[2]);
+        assert_eq!(
+            keys[0],
+            participants[0]
+                .clone()
+                .round4(&forward)
+                .unwrap()
+                .public_key
+                .to_bytes()
+        );
+    }
+}
+
+#[test]
+fn own_public_contributions_sum_to_public_key() {
+    let mut participants = new_participants::<k256::ProjectivePoint>(2, 3);
+    complete_dkg(&mut participants);
+    let sum = participants
+        .iter()
+        .map(|p| p.own_public_contribution())
+        .sum::<k256::ProjectivePoint>();
+    assert_eq!(participants[0].get_public_key(), Some(sum));
+}
+
+#[test]
+fn reset_participants() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    complete_dkg(&mut participants);
+    let public_key = participants[0].get_public_key().unwrap();
+    let copy = participants[0].clone();
+    assert!(copy.get_secret_share().is_some());
+
+    for p in participants.iter_mut() {
+        p.reset().unwrap();
+        assert_eq!(p.get_round(), Round::One);
+        assert!(p.get_valid_participant_ids().is_empty());
+        assert!(p.get_public_key().is_none());
+    }
+    assert_eq!(participants[0].get_id(), 1);
+    // Clones share the protected storage so they are wiped too
+    assert!(copy.get_secret_share().is_none());
+
+    complete_dkg(&mut participants);
+    assert_ne!(participants[0].get_public_key().unwrap(), public_key);
+}
+
+#[test]
+fn streaming_serialization() {
+    type G = k256::ProjectivePoint;
+    let mut participants = new_participants::<G>(2, 3);
+    let (bdata, p2p) = run_round1(&mut participants);
+    let (bmap, p2pmap) = round2_inputs(1, &bdata, &p2p);
+    participants[0].round2(bmap, p2pmap).unwrap();
+
+    let mut writer = Vec::new();
+    participants[0].serialize_to_writer(&mut writer).unwrap();
+    assert_eq!(writer, serde_bare::to_vec(&participants[0]).unwrap());
+
+    let restored = SecretParticipant::<G>::deserialize_from_reader(writer.as_slice()).unwrap();
+    assert_eq!(restored.get_round(), Round::Three);
+    assert_eq!(serde_bare::to_vec(&restored).unwrap(), writer);
+    assert!(SecretParticipant::<G>::deserialize_from_reader(&writer[..writer.len() / 2]).is_err());
+}